@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use zen_internals::sql_injection::detect_sql_injection::{
+    detect_sql_injection_batch, detect_sql_injection_str, detect_sql_injection_with_max_len,
+};
+use zen_internals::sql_injection::sql_injection_detector::SqlInjectionDetector;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let sql = "SELECT * FROM users WHERE id = '1' OR 1=1 # '";
+    let dialect = 8; // MySQL
+
+    let mut group = c.benchmark_group("sql");
+
+    group.bench_function("big sql with max len", |b| {
+        let sql = "SELECT * FROM users WHERE id = 'hello world' ".to_owned()
+            + &" OR id = 'hello world'".repeat(1000)
+            + "; SELECT * FROM users WHERE name = 'goodbye'";
+        b.iter(|| {
+            detect_sql_injection_with_max_len(
+                black_box(&sql),
+                black_box("goodbye"), // user input
+                black_box(dialect),
+                black_box(4096), // max length
+            )
+        });
+    });
+
+    // The same query executed over and over again, the detector only tokenizes it once.
+    let template = "SELECT id, name, email, created_at FROM users WHERE id = 'hello world' AND deleted_at IS NULL ORDER BY created_at DESC LIMIT 10";
+
+    group.bench_function("repeated query", |b| {
+        b.iter(|| {
+            detect_sql_injection_str(
+                black_box(template),
+                black_box("hello world"),
+                black_box(dialect),
+            )
+        })
+    });
+
+    group.bench_function("repeated query with detector", |b| {
+        let detector = SqlInjectionDetector::new(256);
+        b.iter(|| {
+            detector.detect(
+                black_box(template),
+                black_box("hello world"),
+                black_box(dialect),
+            )
+        })
+    });
+
+    group.bench_function("batch of user inputs", |b| {
+        let user_inputs = ["1", "1' OR 1=1 # ", "users", "hello world", "id"];
+        b.iter(|| {
+            detect_sql_injection_batch(black_box(sql), black_box(&user_inputs), black_box(dialect))
+        });
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -12,10 +12,98 @@ const lib = Deno.dlopen(fullTargetDir, {
         parameters: ["pointer", "usize", "pointer", "usize", "i32"],
         result: "i32",
     },
+    detect_shell_injection: {
+        parameters: ["pointer", "usize", "pointer", "usize"],
+        result: "i32",
+    },
+    detect_ssti: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "i32",
+    },
+    detect_path_traversal: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "i32",
+    },
+    detect_sql_injection_with_dialect_name: {
+        parameters: ["pointer", "usize", "pointer", "usize", "pointer", "usize"],
+        result: "i32",
+    },
+    set_sql_injection_exemptions: {
+        parameters: ["pointer", "usize"],
+        result: "i32",
+    },
+    sql_dialect_from_name: {
+        parameters: ["pointer", "usize"],
+        result: "i32",
+    },
+    idor_analyze_sql_with_dialect_name_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize"],
+        result: "pointer",
+    },
     idor_analyze_sql_ffi: {
         parameters: ["pointer", "usize", "i32"],
         result: "pointer",
     },
+    ssrf_analyze_url_ffi: {
+        parameters: ["pointer", "usize"],
+        result: "pointer",
+    },
+    detect_nosql_injection_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize"],
+        result: "pointer",
+    },
+    detect_sql_injection_details_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    detect_sql_injection_with_decodings_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32", "i32"],
+        result: "pointer",
+    },
+    detect_sql_injection_per_occurrence_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    detect_js_injection_details_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    detect_js_injection_per_occurrence_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    detect_sql_injection_with_max_len_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32", "usize"],
+        result: "pointer",
+    },
+    detect_sql_injection_auto_dialect_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize"],
+        result: "pointer",
+    },
+    get_sql_user_input_contexts_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    explain_sql_injection_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    detect_sql_injection_batch_ffi: {
+        parameters: ["pointer", "usize", "pointer", "usize", "i32"],
+        result: "pointer",
+    },
+    sql_injection_detector_new: {
+        parameters: ["usize"],
+        result: "pointer",
+    },
+    sql_injection_detector_detect: {
+        parameters: ["pointer", "pointer", "usize", "pointer", "usize", "i32"],
+        result: "i32",
+    },
+    sql_injection_detector_free: {
+        parameters: ["pointer"],
+        result: "void",
+    },
     free_string: {
         parameters: ["pointer"],
         result: "void",
@@ -53,14 +141,38 @@ assertEquals(
     0
 );
 
-// Test fallback to generic dialect
+// Test SQL injection detector that caches the tokens of queries
+const detector = lib.symbols.sql_injection_detector_new(16);
+for (let i = 0; i < 2; i++) {
+    assertEquals(
+        lib.symbols.sql_injection_detector_detect(
+            detector,
+            ...getBufferAndLength("SELECT * FROM users WHERE id = '' OR 1=1 -- '"),
+            ...getBufferAndLength("' OR 1=1 -- "),
+            0
+        ),
+        1
+    );
+    assertEquals(
+        lib.symbols.sql_injection_detector_detect(
+            detector,
+            ...getBufferAndLength("SELECT * FROM users WHERE id = 'hello world'"),
+            ...getBufferAndLength("hello world"),
+            0
+        ),
+        0
+    );
+}
+lib.symbols.sql_injection_detector_free(detector);
+
+// Test unknown dialect
 assertEquals(
     lib.symbols.detect_sql_injection(
         ...getBufferAndLength("SELECT * FROM users WHERE id = '' OR 1=1 -- '"),
         ...getBufferAndLength("' OR 1=1 -- "),
         2141
     ),
-    1
+    4
 );
 
 // Test unsafe pointer
@@ -117,6 +229,46 @@ assertEquals(
     0
 );
 
+// Failed to parse the code
+assertEquals(
+    lib.symbols.detect_js_injection(
+        ...getBufferAndLength("const test = 'Hello World!"),
+        ...getBufferAndLength("Hello World!"),
+        0
+    ),
+    3
+);
+
+function callDetectJsInjectionDetails(code: string, userinput: string, sourcetype: number): unknown {
+    const resultPtr = lib.symbols.detect_js_injection_details_ffi(
+        ...getBufferAndLength(code),
+        ...getBufferAndLength(userinput),
+        sourcetype
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(callDetectJsInjectionDetails("const test = 'Hello World!'; //';", "Hello World!'; //", 0), {
+    detected: true,
+    reason: "CommentsChanged",
+});
+assertEquals(callDetectJsInjectionDetails("class A { #secret = 1; get(obj) { return obj.#secret; } }", "#secret", 0), {
+    detected: true,
+    reason: "StatementsChanged",
+    changed_node: { index: 5, original: "PrivateIdentifier", safe: "IdentifierName" },
+});
+assertEquals(callDetectJsInjectionDetails("const msg = `Hello ${alert(1)}!`;", "${alert(1)}", 0), {
+    detected: true,
+    reason: "TemplateLiteralBreakout",
+    changed_node: { index: 4, original: "TemplateLiteral(2)", safe: "TemplateLiteral(1)" },
+});
+assertEquals(callDetectJsInjectionDetails("const test = 'Hello World!", "Hello World!", 0), {
+    detected: false,
+    reason: "FailedToParseCode",
+});
+
 // Test unsafe pointer
 assertEquals(
     lib.symbols.detect_js_injection(
@@ -263,6 +415,94 @@ assertEquals(
     1
 );
 
+// Test shell injection
+assertEquals(
+    lib.symbols.detect_shell_injection(
+        ...getBufferAndLength("ls; whoami"),
+        ...getBufferAndLength("; whoami")
+    ),
+    1
+);
+
+// Not an injection
+assertEquals(
+    lib.symbols.detect_shell_injection(
+        ...getBufferAndLength("ls -la /tmp"),
+        ...getBufferAndLength("/tmp")
+    ),
+    0
+);
+
+// Test unsafe pointer
+assertEquals(
+    lib.symbols.detect_shell_injection(null, 0, ...getBufferAndLength("🔥")),
+    2
+);
+assertEquals(
+    lib.symbols.detect_shell_injection(
+        toCStringInvalidUtf8(),
+        2,
+        ...getBufferAndLength("test")
+    ),
+    2
+);
+
+// Test server-side template injection
+assertEquals(
+    lib.symbols.detect_ssti(
+        ...getBufferAndLength("Hello {{7*7}}!"),
+        ...getBufferAndLength("{{7*7}}"),
+        0
+    ),
+    1
+);
+
+// Not an injection
+assertEquals(
+    lib.symbols.detect_ssti(
+        ...getBufferAndLength("Hello {{ name }}, welcome"),
+        ...getBufferAndLength("welcome"),
+        2
+    ),
+    0
+);
+
+// Test unsafe pointer
+assertEquals(
+    lib.symbols.detect_ssti(null, 0, ...getBufferAndLength("🔥"), 0),
+    2
+);
+
+// Test path traversal
+assertEquals(
+    lib.symbols.detect_path_traversal(
+        ...getBufferAndLength("/var/www/uploads/../../etc/passwd"),
+        ...getBufferAndLength("../../etc/passwd"),
+        0
+    ),
+    1
+);
+
+// Not a path traversal
+assertEquals(
+    lib.symbols.detect_path_traversal(
+        ...getBufferAndLength("/var/www/uploads/image.png"),
+        ...getBufferAndLength("image.png"),
+        0
+    ),
+    0
+);
+
+// Test decoding failure
+assertEquals(
+    lib.symbols.detect_path_traversal(
+        ...getBufferAndLength("/var/www/uploads/image.png"),
+        ...getBufferAndLength("%C3"),
+        0
+    ),
+    3
+);
+
 // Test IDOR SQL analysis
 function callIdorAnalyzeSql(query: string, dialect: number): unknown {
     const [queryPtr, queryLen] = getBufferAndLength(query);
@@ -297,10 +537,349 @@ assertEquals(
     { error: "Invalid query pointer or length" }
 );
 
+assertEquals(
+    callIdorAnalyzeSql("SELECT * FROM users WHERE tenant_id = $1", 2141),
+    { error: "Unknown SQL dialect: 2141" }
+);
+
 // Test transaction-related queries
 assertEquals(
     callIdorAnalyzeSql("COMMIT", 9),
     []
 );
 
+// Test dialect names
+assertEquals(
+    lib.symbols.detect_sql_injection_with_dialect_name(
+        ...getBufferAndLength("SELECT * FROM users WHERE id = '' OR 1=1 -- '"),
+        ...getBufferAndLength("' OR 1=1 -- "),
+        ...getBufferAndLength("postgres")
+    ),
+    1
+);
+
+assertEquals(
+    lib.symbols.detect_sql_injection_with_dialect_name(
+        ...getBufferAndLength("SELECT * FROM users WHERE id = '' OR 1=1 -- '"),
+        ...getBufferAndLength("' OR 1=1 -- "),
+        ...getBufferAndLength("postgress")
+    ),
+    4
+);
+
+assertEquals(lib.symbols.sql_dialect_from_name(...getBufferAndLength("MySQL")), 8);
+assertEquals(lib.symbols.sql_dialect_from_name(...getBufferAndLength("unknown")), -1);
+
+function callIdorAnalyzeSqlWithDialectName(query: string, dialect: string): unknown {
+    const resultPtr = lib.symbols.idor_analyze_sql_with_dialect_name_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(dialect)
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callIdorAnalyzeSqlWithDialectName("SELECT * FROM users WHERE tenant_id = $1", "postgres"),
+    [{ kind: "select", tables: [{ name: "users" }], filters: [{ column: "tenant_id", value: "$1", is_placeholder: true }] }]
+);
+
+assertEquals(
+    callIdorAnalyzeSqlWithDialectName("SELECT * FROM users WHERE tenant_id = $1", "postgress"),
+    { error: "Unknown SQL dialect: postgress" }
+);
+
+// Test SSRF URL analysis
+function callSsrfAnalyzeUrl(url: string): unknown {
+    const [urlPtr, urlLen] = getBufferAndLength(url);
+    const resultPtr = lib.symbols.ssrf_analyze_url_ffi(urlPtr, urlLen);
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callSsrfAnalyzeUrl("http://[::ffff:127.0.0.1]:8080/admin"),
+    { hostname: "::ffff:127.0.0.1", port: 8080, ip: "::ffff:127.0.0.1", is_private: false, is_loopback: true, is_link_local: false, is_metadata: false }
+);
+
+assertEquals(
+    callSsrfAnalyzeUrl("http://example.com:99999"),
+    { error: "Invalid port" }
+);
+
+assertEquals(
+    (() => {
+        const resultPtr = lib.symbols.ssrf_analyze_url_ffi(null, 0);
+        const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+        lib.symbols.free_string(resultPtr);
+        return JSON.parse(result);
+    })(),
+    { error: "Invalid URL pointer or length" }
+);
+
+// Test NoSQL injection
+function callDetectNoSqlInjection(filter: string, userinput: string): unknown {
+    const resultPtr = lib.symbols.detect_nosql_injection_ffi(
+        ...getBufferAndLength(filter),
+        ...getBufferAndLength(userinput)
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callDetectNoSqlInjection('{"username": "admin", "password": {"$ne": null}}', '{"password": {"$ne": null}}'),
+    { detected: true, user_input_path: ".password", filter_path: ".password", operators: ["$ne"] }
+);
+
+assertEquals(
+    callDetectNoSqlInjection('{"username": "admin"}', '{"username": "admin"}'),
+    { detected: false }
+);
+
+// Test SQL injection details
+function callDetectSqlInjectionDetails(query: string, userinput: string, dialect: number): unknown {
+    const resultPtr = lib.symbols.detect_sql_injection_details_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(userinput),
+        dialect
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callDetectSqlInjectionDetails("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "' OR 1=1 -- ", 0),
+    { detected: true, reason: "TokensHaveDelta", span: { start: 32, end: 44 } }
+);
+
+assertEquals(
+    callDetectSqlInjectionDetails("SELECT * FROM users WHERE id = 'hello world'", "hello world", 0),
+    { detected: false, reason: "NoChangesFound", span: { start: 32, end: 43 } }
+);
+
+assertEquals(
+    callDetectSqlInjectionDetails("SELECT * FROM users WHERE id = 'hello world'", "hello world", 2141),
+    { error: "Unknown SQL dialect: 2141" }
+);
+
+// Test SQL injection with decodings of the user input
+function callDetectSqlInjectionWithDecodings(
+    query: string,
+    userinput: string,
+    dialect: number,
+    decodings: number
+): unknown {
+    const resultPtr = lib.symbols.detect_sql_injection_with_decodings_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(userinput),
+        dialect,
+        decodings
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callDetectSqlInjectionWithDecodings("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "&#39; OR 1=1 -- ", 9, 2),
+    { detected: true, reason: "TokensHaveDelta", span: { start: 32, end: 44 }, decoding: "HtmlEntityDecode" }
+);
+
+// Test per-occurrence detection
+function callPerOccurrence(
+    symbol: "detect_sql_injection_per_occurrence_ffi" | "detect_js_injection_per_occurrence_ffi",
+    code: string,
+    userinput: string,
+    type: number
+): unknown {
+    const resultPtr = lib.symbols[symbol](
+        ...getBufferAndLength(code),
+        ...getBufferAndLength(userinput),
+        type
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callPerOccurrence(
+        "detect_sql_injection_per_occurrence_ffi",
+        "SELECT * FROM users WHERE name = '1 OR 1=1' OR id = 1 OR 1=1",
+        "1 OR 1=1",
+        9
+    ),
+    { detected: true, reason: "TokensHaveDelta", span: { start: 52, end: 60 } }
+);
+
+assertEquals(
+    callPerOccurrence(
+        "detect_js_injection_per_occurrence_ffi",
+        "const a = \"1; alert(1)\"; const b = 1; alert(1)",
+        "1; alert(1)",
+        0
+    ),
+    {
+        detected: true,
+        reason: "StatementsChanged",
+        changed_node: { index: 9, original: "ExpressionStatement", safe: null },
+        span: { start: 35, end: 46 },
+    }
+);
+
+assertEquals(
+    callPerOccurrence("detect_js_injection_per_occurrence_ffi", "const a = 'Hello World!';", "Hello World!", 0),
+    { detected: false, reason: "NoChangesFound", span: { start: 11, end: 23 } }
+);
+
+// Test SQL injection detection in huge queries
+function callWithMaxLen(query: string, userinput: string, dialect: number, maxLen: number): unknown {
+    const resultPtr = lib.symbols.detect_sql_injection_with_max_len_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(userinput),
+        dialect,
+        maxLen
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callWithMaxLen("INSERT INTO logs (message) VALUES ('hello world'), ('hello world')", "hello world", 9, 32),
+    { detected: false, reason: "QueryTooLarge", span: { start: 36, end: 47 } }
+);
+
+assertEquals(
+    callWithMaxLen(
+        "INSERT INTO logs (message) VALUES ('hello world'), ('hello world'); SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+        "' OR 1=1 -- ",
+        9,
+        64
+    ),
+    { detected: true, reason: "TokensHaveDelta", span: { start: 100, end: 112 } }
+);
+
+// Test SQL injection detection with an unknown dialect
+function callAutoDialect(query: string, userinput: string): unknown {
+    const resultPtr = lib.symbols.detect_sql_injection_auto_dialect_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(userinput)
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callAutoDialect("SELECT * FROM users WHERE id = 1 /* /* */ OR 1=1 -- */", "/* */ OR 1=1 -- "),
+    {
+        detected: true,
+        reason: "TokensHaveDelta",
+        span: { start: 36, end: 52 },
+        dialect: "MySql",
+        dialects_evaluated: ["Generic", "MySql"],
+    }
+);
+
+// Test the context of the user input in SQL queries
+function callUserInputContexts(query: string, userinput: string, dialect: number): unknown {
+    const resultPtr = lib.symbols.get_sql_user_input_contexts_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(userinput),
+        dialect
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callUserInputContexts("SELECT * FROM users WHERE id = 1 OR 1=1 AND name = '1 OR 1=1'", "1 OR 1=1", 9),
+    [
+        { span: { start: 31, end: 39 }, context: "Raw" },
+        { span: { start: 52, end: 60 }, context: "StringLiteral" },
+    ]
+);
+
+assertEquals(callUserInputContexts("SELECT 'abc", "abc", 0), { error: "Failed to tokenize query" });
+
+// Test SQL injection explanation
+function callExplainSqlInjection(query: string, userinput: string, dialect: number): unknown {
+    const resultPtr = lib.symbols.explain_sql_injection_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(userinput),
+        dialect
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callExplainSqlInjection("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 9),
+    {
+        detected: true,
+        reason: "TokensHaveDelta",
+        span: { start: 31, end: 39 },
+        token_diff: {
+            window_start: 10,
+            original_tokens: ["id", " ", "=", " ", "1", " ", "OR", " ", "1", "=", "1"],
+            safe_tokens: ["id", " ", "=", " ", "aaaaaaaa"],
+            first_difference: { index: 15, original: " " },
+        },
+    }
+);
+
+// Test SQL injection batch
+function callDetectSqlInjectionBatch(query: string, userinputs: string[], dialect: number): unknown {
+    const resultPtr = lib.symbols.detect_sql_injection_batch_ffi(
+        ...getBufferAndLength(query),
+        ...getBufferAndLength(JSON.stringify(userinputs)),
+        dialect
+    );
+    const result = new Deno.UnsafePointerView(resultPtr!).getCString();
+    lib.symbols.free_string(resultPtr);
+    return JSON.parse(result);
+}
+
+assertEquals(
+    callDetectSqlInjectionBatch("SELECT * FROM users WHERE id = '' OR 1=1 -- '", ["' OR 1=1 -- ", "users", "goodbye"], 0),
+    [
+        { detected: true, reason: "TokensHaveDelta", span: { start: 32, end: 44 } },
+        { detected: false, reason: "NoChangesFound", span: { start: 14, end: 19 } },
+        { detected: false, reason: "UserInputNotInQuery" },
+    ]
+);
+
+assertEquals(
+    callDetectSqlInjectionBatch("SELECT 1", ["1"], 2141),
+    { error: "Unknown SQL dialect: 2141" }
+);
+
+// Test custom SQL injection exemptions
+const filterQuery = "SELECT * FROM products WHERE price > 10";
+assertEquals(callDetectSqlInjectionBatch(filterQuery, ["price > 10"], 9), [
+    { detected: true, reason: "TokensHaveDelta", span: { start: 29, end: 39 } },
+]);
+assertEquals(
+    lib.symbols.set_sql_injection_exemptions(...getBufferAndLength('{"strings":["price > 10"]}')),
+    0
+);
+assertEquals(callDetectSqlInjectionBatch(filterQuery, ["price > 10"], 9), [
+    {
+        detected: false,
+        reason: "CommonSQLString",
+        span: { start: 29, end: 39 },
+        exemption: { kind: "CustomString", rule: "price > 10" },
+    },
+]);
+assertEquals(lib.symbols.set_sql_injection_exemptions(...getBufferAndLength('{"regexes":["("]}')), 2);
+assertEquals(lib.symbols.set_sql_injection_exemptions(...getBufferAndLength("{}")), 0);
+
 lib.close();
@@ -8,6 +8,155 @@ test("wasm_detect_sql_injection", () => {
 
  // Tokenize error
  deepStrictEqual(internals.wasm_detect_sql_injection('SELECT unicorns fly over the "rainbow', "rainbow"), 3);
+
+ // Unknown dialect
+ deepStrictEqual(internals.wasm_detect_sql_injection("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "' OR 1=1 -- ", 2141), 4);
+});
+
+test("SqlInjectionDetector", () => {
+ const detector = new internals.SqlInjectionDetector(16);
+ for (let i = 0; i < 2; i++) {
+  deepStrictEqual(detector.detect("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "' OR 1=1 -- ", 0), 1);
+  deepStrictEqual(detector.detect("SELECT * FROM users WHERE id = 'hello world'", "hello world", 0), 0);
+  deepStrictEqual(detector.detect('SELECT unicorns fly over the "rainbow', "rainbow", 0), 3);
+ }
+ detector.free();
+});
+
+test("wasm_detect_sql_injection_with_dialect_name", () => {
+ deepStrictEqual(internals.wasm_detect_sql_injection_with_dialect_name("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "' OR 1=1 -- ", "postgres"), 1);
+ deepStrictEqual(internals.wasm_detect_sql_injection_with_dialect_name("SELECT * FROM users WHERE id = 'hello world'", "hello world", "MySQL"), 0);
+ deepStrictEqual(internals.wasm_detect_sql_injection_with_dialect_name("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "' OR 1=1 -- ", "postgress"), 4);
+});
+
+test("wasm_sql_dialect_from_name", () => {
+ deepStrictEqual(internals.wasm_sql_dialect_from_name("postgres"), 9);
+ deepStrictEqual(internals.wasm_sql_dialect_from_name("mariadb"), 14);
+ deepStrictEqual(internals.wasm_sql_dialect_from_name("unknown"), -1);
+});
+
+test("wasm_detect_sql_injection_details", () => {
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_details("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "' OR 1=1 -- ", 0),
+  { detected: true, reason: "TokensHaveDelta", span: { start: 32, end: 44 } }
+ );
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_details("SELECT * FROM users WHERE id = 'hello world'", "goodbye", 0),
+  { detected: false, reason: "UserInputNotInQuery" }
+ );
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_details("SELECT * FROM users WHERE id = 'hello world'", "hello world", 2141),
+  { error: "Unknown SQL dialect: 2141" }
+ );
+});
+
+test("wasm_detect_sql_injection_with_decodings", () => {
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_with_decodings("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "%27%20OR%201%3D1%20--%20", 9, 1 | 2),
+  { detected: true, reason: "TokensHaveDelta", span: { start: 32, end: 44 }, decoding: "UrlDecode" }
+ );
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_with_decodings("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "%27%20OR%201%3D1%20--%20", 9, 0),
+  { detected: false, reason: "UserInputNotInQuery" }
+ );
+});
+
+test("wasm_detect_sql_injection_per_occurrence", () => {
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_per_occurrence("SELECT * FROM users WHERE name = '1 OR 1=1' OR id = 1 OR 1=1", "1 OR 1=1", 9),
+  { detected: true, reason: "TokensHaveDelta", span: { start: 52, end: 60 } }
+ );
+});
+
+test("wasm_detect_sql_injection_with_max_len", () => {
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_with_max_len("INSERT INTO logs (message) VALUES ('hello world'), ('hello world')", "hello world", 9, 32),
+  { detected: false, reason: "QueryTooLarge", span: { start: 36, end: 47 } }
+ );
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_with_max_len(
+   "INSERT INTO logs (message) VALUES ('hello world'), ('hello world'); SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+   "' OR 1=1 -- ",
+   9,
+   64
+  ),
+  { detected: true, reason: "TokensHaveDelta", span: { start: 100, end: 112 } }
+ );
+});
+
+test("wasm_detect_sql_injection_auto_dialect", () => {
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_auto_dialect("SELECT * FROM users WHERE id = 1 /* /* */ OR 1=1 -- */", "/* */ OR 1=1 -- "),
+  {
+   detected: true,
+   reason: "TokensHaveDelta",
+   span: { start: 36, end: 52 },
+   dialect: "MySql",
+   dialects_evaluated: ["Generic", "MySql"],
+  }
+ );
+});
+
+test("wasm_get_sql_user_input_contexts", () => {
+ deepStrictEqual(
+  internals.wasm_get_sql_user_input_contexts("SELECT * FROM users WHERE id = 1 OR 1=1 AND name = '1 OR 1=1'", "1 OR 1=1", 9),
+  [
+   { span: { start: 31, end: 39 }, context: "Raw" },
+   { span: { start: 52, end: 60 }, context: "StringLiteral" },
+  ]
+ );
+ deepStrictEqual(internals.wasm_get_sql_user_input_contexts("SELECT 'abc", "abc", 0), { error: "Failed to tokenize query" });
+});
+
+test("wasm_explain_sql_injection", () => {
+ deepStrictEqual(
+  internals.wasm_explain_sql_injection("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 9),
+  {
+   detected: true,
+   reason: "TokensHaveDelta",
+   span: { start: 31, end: 39 },
+   token_diff: {
+    window_start: 10,
+    original_tokens: ["id", " ", "=", " ", "1", " ", "OR", " ", "1", "=", "1"],
+    safe_tokens: ["id", " ", "=", " ", "aaaaaaaa"],
+    first_difference: { index: 15, original: " " },
+   },
+  }
+ );
+ deepStrictEqual(
+  internals.wasm_explain_sql_injection("SELECT * FROM users WHERE id = 'hello world'", "goodbye", 0),
+  { detected: false, reason: "UserInputNotInQuery" }
+ );
+});
+
+test("wasm_detect_sql_injection_batch", () => {
+ deepStrictEqual(
+  internals.wasm_detect_sql_injection_batch("SELECT * FROM users WHERE id = '' OR 1=1 -- '", ["' OR 1=1 -- ", "users", "goodbye"], 0),
+  [
+   { detected: true, reason: "TokensHaveDelta", span: { start: 32, end: 44 } },
+   { detected: false, reason: "NoChangesFound", span: { start: 14, end: 19 } },
+   { detected: false, reason: "UserInputNotInQuery" },
+  ]
+ );
+ deepStrictEqual(internals.wasm_detect_sql_injection_batch("SELECT 1", [], 0), []);
+ deepStrictEqual(internals.wasm_detect_sql_injection_batch("SELECT 1", ["1"], 2141), { error: "Unknown SQL dialect: 2141" });
+});
+
+test("wasm_set_sql_injection_exemptions", () => {
+ const query = "SELECT * FROM products WHERE price > 10";
+ deepStrictEqual(internals.wasm_detect_sql_injection(query, "price > 10", 9), 1);
+ deepStrictEqual(internals.wasm_set_sql_injection_exemptions('{"strings":["price > 10"]}'), undefined);
+ deepStrictEqual(internals.wasm_detect_sql_injection_batch(query, ["price > 10"], 9), [
+  {
+   detected: false,
+   reason: "CommonSQLString",
+   span: { start: 29, end: 39 },
+   exemption: { kind: "CustomString", rule: "price > 10" },
+  },
+ ]);
+ deepStrictEqual(internals.wasm_set_sql_injection_exemptions('{"regexes":["("]}').error.startsWith("Invalid regex: "), true);
+ deepStrictEqual(internals.wasm_set_sql_injection_exemptions("{}"), undefined);
+ deepStrictEqual(internals.wasm_detect_sql_injection(query, "price > 10", 9), 1);
 });
 
 test("wasm_detect_js_injection", () => {
@@ -15,6 +164,60 @@ test("wasm_detect_js_injection", () => {
  deepStrictEqual(internals.wasm_detect_js_injection("const test = 'Hello World!'; //';", "Hello World!", 0), false);
 });
 
+test("wasm_detect_js_injection_details", () => {
+ deepStrictEqual(internals.wasm_detect_js_injection_details("const test = 'Hello World!'; //';", "Hello World!'; //", 0), {
+  detected: true,
+  reason: "CommentsChanged",
+ });
+ deepStrictEqual(internals.wasm_detect_js_injection_details("class A { #secret = 1; get(obj) { return obj.#secret; } }", "#secret", 0), {
+  detected: true,
+  reason: "StatementsChanged",
+  changed_node: { index: 5, original: "PrivateIdentifier", safe: "IdentifierName" },
+ });
+ deepStrictEqual(internals.wasm_detect_js_injection_details("const msg = `Hello ${alert(1)}!`;", "${alert(1)}", 0), {
+  detected: true,
+  reason: "TemplateLiteralBreakout",
+  changed_node: { index: 4, original: "TemplateLiteral(2)", safe: "TemplateLiteral(1)" },
+ });
+ deepStrictEqual(internals.wasm_detect_js_injection_details("const test = 'Hello World!", "Hello World!", 0), {
+  detected: false,
+  reason: "FailedToParseCode",
+ });
+});
+
+test("wasm_detect_js_injection_per_occurrence", () => {
+ deepStrictEqual(internals.wasm_detect_js_injection_per_occurrence("const test = 'Hello World!'; //'; const test2 = 'Hello World!'; //';", "Hello World!'; //", 0), {
+  detected: true,
+  reason: "CommentsChanged",
+  span: { start: 14, end: 31 },
+ });
+ deepStrictEqual(internals.wasm_detect_js_injection_per_occurrence("const a = 'Hello World!'; const b = 'Hello World!';", "Hello World!", 0), {
+  detected: false,
+  reason: "NoChangesFound",
+  span: { start: 11, end: 23 },
+ });
+});
+
+test("wasm_detect_shell_injection", () => {
+ deepStrictEqual(internals.wasm_detect_shell_injection("ls; whoami", "; whoami"), true);
+ deepStrictEqual(internals.wasm_detect_shell_injection("ls -la /tmp", "/tmp"), false);
+});
+
+test("wasm_detect_ssti", () => {
+ deepStrictEqual(internals.wasm_detect_ssti("Hello {{7*7}}!", "{{7*7}}", 0), true);
+ deepStrictEqual(internals.wasm_detect_ssti("Hello <%= 7*7 %>!", "<%= 7*7 %>", 1), true);
+ deepStrictEqual(internals.wasm_detect_ssti("Hello {{ name }}, welcome", "welcome", 2), false);
+});
+
+test("wasm_detect_path_traversal", () => {
+ deepStrictEqual(internals.wasm_detect_path_traversal("/var/www/uploads/../../etc/passwd", "../../etc/passwd", 0), 1);
+ deepStrictEqual(internals.wasm_detect_path_traversal("/var/www/uploads/image.png", "image.png", 0), 0);
+ deepStrictEqual(internals.wasm_detect_path_traversal("C:\\www\\uploads\\..\\win.ini", "..\\win.ini", 1), 1);
+
+ // Decoding error
+ deepStrictEqual(internals.wasm_detect_path_traversal("/var/www/uploads/image.png", "%C3", 0), 3);
+});
+
 test("wasm_idor_analyze_sql", () => {
  deepStrictEqual(
   internals.wasm_idor_analyze_sql("SELECT * FROM users WHERE tenant_id = $1", 9),
@@ -28,9 +231,47 @@ test("wasm_idor_analyze_sql", () => {
   internals.wasm_idor_analyze_sql("INVALID SQL QUERY", 9),
   { error: "sql parser error: Expected: an SQL statement, found: INVALID at Line: 1, Column: 1" }
  );
+ deepStrictEqual(
+  internals.wasm_idor_analyze_sql("SELECT * FROM users WHERE tenant_id = $1", 2141),
+  { error: "Unknown SQL dialect: 2141" }
+ );
  // Test transaction-related queries
  deepStrictEqual(
   internals.wasm_idor_analyze_sql("COMMIT", 9),
   []
  );
 });
+
+test("wasm_idor_analyze_sql_with_dialect_name", () => {
+ deepStrictEqual(
+  internals.wasm_idor_analyze_sql_with_dialect_name("SELECT * FROM users WHERE tenant_id = $1", "postgres"),
+  [{ kind: "select", tables: [{ name: "users" }], filters: [{ column: "tenant_id", value: "$1", is_placeholder: true }] }]
+ );
+ deepStrictEqual(
+  internals.wasm_idor_analyze_sql_with_dialect_name("SELECT * FROM users WHERE tenant_id = $1", "postgress"),
+  { error: "Unknown SQL dialect: postgress" }
+ );
+});
+
+test("wasm_ssrf_analyze_url", () => {
+ deepStrictEqual(
+  internals.wasm_ssrf_analyze_url("http://0x7f.1:8080/admin"),
+  { hostname: "127.0.0.1", port: 8080, ip: "127.0.0.1", is_private: false, is_loopback: true, is_link_local: false, is_metadata: false }
+ );
+ deepStrictEqual(
+  internals.wasm_ssrf_analyze_url("http://example.com@169.254.169.254/latest/meta-data/"),
+  { hostname: "169.254.169.254", ip: "169.254.169.254", is_private: false, is_loopback: false, is_link_local: true, is_metadata: true }
+ );
+ deepStrictEqual(internals.wasm_ssrf_analyze_url("http://example.com:99999"), { error: "Invalid port" });
+});
+
+test("wasm_detect_nosql_injection", () => {
+ deepStrictEqual(
+  internals.wasm_detect_nosql_injection('{"username": "admin", "password": {"$ne": null}}', '{"password": {"$ne": null}}'),
+  { detected: true, user_input_path: ".password", filter_path: ".password", operators: ["$ne"] }
+ );
+ deepStrictEqual(
+  internals.wasm_detect_nosql_injection('{"username": "admin"}', '{"username": "admin"}'),
+  { detected: false }
+ );
+});
@@ -5,8 +5,28 @@
 //! `# Safety` contract that the caller must uphold.
 
 use crate::idor::idor_analyze_sql::idor_analyze_sql;
-use crate::js_injection::detect_js_injection::detect_js_injection_str;
-use crate::sql_injection::detect_sql_injection::{detect_sql_injection_str, DetectionReason};
+use crate::js_injection::detect_js_injection::{
+    detect_js_injection_per_occurrence, detect_js_injection_str,
+    DetectionReason as JsDetectionReason,
+};
+use crate::nosql_injection::detect_nosql_injection::detect_nosql_injection_str;
+use crate::path_traversal::detect_path_traversal::{
+    detect_path_traversal as detect_path_traversal_str, DetectionReason as PathTraversalReason,
+};
+use crate::shell_injection::detect_shell_injection::detect_shell_injection_str;
+use crate::sql_injection::custom_exemptions::set_custom_exemptions_json;
+use crate::sql_injection::detect_sql_injection::{
+    detect_sql_injection_auto_dialect, detect_sql_injection_batch,
+    detect_sql_injection_per_occurrence, detect_sql_injection_str,
+    detect_sql_injection_with_decodings, detect_sql_injection_with_max_len, DetectionReason,
+};
+use crate::sql_injection::explain_sql_injection::explain_sql_injection_str;
+use crate::sql_injection::helpers::select_decodings_based_on_bitmask::select_decodings_based_on_bitmask;
+use crate::sql_injection::sql_dialect::SqlDialect;
+use crate::sql_injection::sql_injection_detector::SqlInjectionDetector;
+use crate::sql_injection::user_input_context::get_user_input_contexts;
+use crate::ssrf::ssrf_analyze_url::ssrf_analyze_url;
+use crate::ssti::detect_ssti::detect_ssti_str;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_int};
 use std::panic;
@@ -15,6 +35,20 @@ use std::str;
 #[cfg(target_arch = "wasm32")]
 use std::alloc::{alloc, dealloc, Layout};
 
+// Checks that `dialect` is one of the numbers of `SqlDialect`, e.g. `Unknown SQL dialect: 99`.
+fn validate_dialect(dialect: c_int) -> Result<(), String> {
+    SqlDialect::try_from(dialect).map(|_| ())
+}
+
+// Returns `{"error":"..."}` for the functions that return JSON, e.g. for an unknown SQL dialect.
+fn error_json(error: String) -> *mut c_char {
+    CString::new(serde_json::json!({ "error": error }).to_string())
+        .unwrap()
+        .into_raw()
+}
+
+/// Returns 4 if `dialect` is not one of the numbers of `SqlDialect`.
+///
 /// # Safety
 ///
 /// `query` and `userinput` must each be null or point to an initialized buffer
@@ -29,6 +63,10 @@ pub unsafe extern "C" fn detect_sql_injection(
 ) -> c_int {
     // Returns an integer value, representing a boolean (1 = true, 0 = false, 2 = error)
     panic::catch_unwind(|| {
+        if validate_dialect(dialect).is_err() {
+            return 4; // Return unknown dialect code
+        }
+
         // Check if the pointers are null
         if query.is_null() || userinput.is_null() {
             return 2;
@@ -66,6 +104,204 @@ pub unsafe extern "C" fn detect_sql_injection(
     .unwrap_or(2)
 }
 
+/// Creates a detector that caches the tokens of up to `capacity` queries (0 disables the cache),
+/// for applications that execute the same queries over and over again. The detector can be
+/// shared between threads and must be freed with `sql_injection_detector_free`.
+#[no_mangle]
+pub extern "C" fn sql_injection_detector_new(capacity: usize) -> *mut SqlInjectionDetector {
+    Box::into_raw(Box::new(SqlInjectionDetector::new(capacity)))
+}
+
+/// Same as `detect_sql_injection`, but the tokens of the query are cached by the detector.
+///
+/// # Safety
+///
+/// `detector` must be null or a pointer returned by `sql_injection_detector_new` that has not
+/// been freed. `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn sql_injection_detector_detect(
+    detector: *const SqlInjectionDetector,
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+) -> c_int {
+    // Returns an integer value, representing a boolean (1 = true, 0 = false, 2 = error)
+    panic::catch_unwind(|| {
+        if validate_dialect(dialect).is_err() {
+            return 4; // Return unknown dialect code
+        }
+
+        if detector.is_null() || query.is_null() || userinput.is_null() {
+            return 2;
+        }
+
+        if query_len == 0 || userinput_len == 0 {
+            return 2;
+        }
+
+        // SAFETY: caller guarantees a valid detector, per the `# Safety` contract.
+        let detector = unsafe { &*detector };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        let query_str = match str::from_utf8(query_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let userinput_str = match str::from_utf8(userinput_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        let detection_results = detector.detect(query_str, userinput_str, dialect);
+        if let DetectionReason::FailedToTokenizeQuery = detection_results.reason {
+            // make a special exception for failing to tokenize query (report code 3)
+            return 3;
+        }
+        if detection_results.detected {
+            return 1;
+        }
+
+        0
+    })
+    .unwrap_or(2)
+}
+
+/// # Safety
+///
+/// `detector` must be null or a pointer returned by `sql_injection_detector_new`.
+/// It must not have been freed already, and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn sql_injection_detector_free(detector: *mut SqlInjectionDetector) {
+    if !detector.is_null() {
+        drop(Box::from_raw(detector));
+    }
+}
+
+/// Same as `detect_sql_injection`, but the dialect is passed by name (e.g. `postgres` or `mysql`,
+/// see `SqlDialect::from_str`). Returns 4 if the dialect is unknown.
+///
+/// # Safety
+///
+/// `query`, `userinput` and `dialect` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len`/`dialect_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_with_dialect_name(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: *const u8,
+    dialect_len: usize,
+) -> c_int {
+    // Returns an integer value, representing a boolean (1 = true, 0 = false, 2 = error)
+    panic::catch_unwind(|| {
+        // Check if the pointers are null
+        if query.is_null() || userinput.is_null() || dialect.is_null() {
+            return 2;
+        }
+
+        if query_len == 0 || userinput_len == 0 || dialect_len == 0 {
+            return 2;
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        let query_str = match str::from_utf8(query_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let userinput_str = match str::from_utf8(userinput_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let dialect_bytes = unsafe { std::slice::from_raw_parts(dialect, dialect_len) };
+        let dialect = match str::from_utf8(dialect_bytes).map(str::parse::<SqlDialect>) {
+            Ok(Ok(dialect)) => dialect,
+            Ok(Err(_)) => return 4, // Return unknown dialect code
+            Err(_) => return 2,     // Return error code if invalid UTF-8
+        };
+
+        let detection_results = detect_sql_injection_str(query_str, userinput_str, dialect as i32);
+        if let DetectionReason::FailedToTokenizeQuery = detection_results.reason {
+            // make a special exception for failing to tokenize query (report code 3)
+            return 3;
+        }
+        if detection_results.detected {
+            return 1;
+        }
+
+        0
+    })
+    .unwrap_or(2)
+}
+
+/// Returns the number of the SQL dialect with the given name (e.g. `postgres` -> 9), to pass to
+/// the functions that take the dialect as a number. Returns -1 if the dialect is unknown.
+///
+/// # Safety
+///
+/// `name` must be null or point to an initialized buffer of at least
+/// `name_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn sql_dialect_from_name(name: *const u8, name_len: usize) -> c_int {
+    panic::catch_unwind(|| {
+        if name.is_null() || name_len == 0 {
+            return -1;
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let name_bytes = unsafe { std::slice::from_raw_parts(name, name_len) };
+        match str::from_utf8(name_bytes).map(str::parse::<SqlDialect>) {
+            Ok(Ok(dialect)) => dialect as c_int,
+            _ => -1,
+        }
+    })
+    .unwrap_or(-1)
+}
+
+/// Sets the custom exemptions for SQL injection detection from JSON, replacing the previous ones,
+/// e.g. `{"strings":["price > 10"],"regexes":["^price (<|>) [0-9]+$"]}`.
+/// Returns 0 on success, or 2 if the JSON or one of the regexes is invalid (the previous
+/// exemptions are kept).
+/// The exemptions apply to every detection of the process (including detectors), not only to
+/// the caller. A regex has to match the whole user input.
+///
+/// # Safety
+///
+/// `exemptions` must be null or point to an initialized buffer of at least
+/// `exemptions_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn set_sql_injection_exemptions(
+    exemptions: *const u8,
+    exemptions_len: usize,
+) -> c_int {
+    panic::catch_unwind(|| {
+        if exemptions.is_null() || exemptions_len == 0 {
+            return 2;
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let exemptions_bytes = unsafe { std::slice::from_raw_parts(exemptions, exemptions_len) };
+        match str::from_utf8(exemptions_bytes).map(set_custom_exemptions_json) {
+            Ok(Ok(())) => 0,
+            _ => 2,
+        }
+    })
+    .unwrap_or(2)
+}
+
 /// # Safety
 ///
 /// `code` and `userinput` must each be null or point to an initialized buffer
@@ -103,7 +339,154 @@ pub unsafe extern "C" fn detect_js_injection(
             Err(_) => return 2, // Return error code if invalid UTF-8
         };
 
-        if detect_js_injection_str(code_str, userinput_str, sourcetype) {
+        let detection_results = detect_js_injection_str(code_str, userinput_str, sourcetype);
+        if let JsDetectionReason::FailedToParseCode = detection_results.reason {
+            // make a special exception for failing to parse the code (report code 3)
+            return 3;
+        }
+        if detection_results.detected {
+            return 1;
+        }
+
+        0
+    })
+    .unwrap_or(2)
+}
+
+/// # Safety
+///
+/// `command` and `userinput` must each be null or point to an initialized buffer
+/// of at least `command_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_shell_injection(
+    command: *const u8,
+    command_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+) -> c_int {
+    // Returns an integer value, representing a boolean (1 = true, 0 = false, 2 = error)
+    panic::catch_unwind(|| {
+        // Check if the pointers are null
+        if command.is_null() || userinput.is_null() {
+            return 2;
+        }
+
+        if command_len == 0 || userinput_len == 0 {
+            return 2;
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let command_bytes = unsafe { std::slice::from_raw_parts(command, command_len) };
+        let command_str = match str::from_utf8(command_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let userinput_str = match str::from_utf8(userinput_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        if detect_shell_injection_str(command_str, userinput_str) {
+            return 1;
+        }
+
+        0
+    })
+    .unwrap_or(2)
+}
+
+/// # Safety
+///
+/// `template` and `userinput` must each be null or point to an initialized buffer
+/// of at least `template_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_ssti(
+    template: *const u8,
+    template_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    engine: c_int,
+) -> c_int {
+    // Returns an integer value, representing a boolean (1 = true, 0 = false, 2 = error)
+    panic::catch_unwind(|| {
+        // Check if the pointers are null
+        if template.is_null() || userinput.is_null() {
+            return 2;
+        }
+
+        if template_len == 0 || userinput_len == 0 {
+            return 2;
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let template_bytes = unsafe { std::slice::from_raw_parts(template, template_len) };
+        let template_str = match str::from_utf8(template_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let userinput_str = match str::from_utf8(userinput_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        if detect_ssti_str(template_str, userinput_str, engine) {
+            return 1;
+        }
+
+        0
+    })
+    .unwrap_or(2)
+}
+
+/// # Safety
+///
+/// `file_path` and `userinput` must each be null or point to an initialized buffer
+/// of at least `file_path_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_path_traversal(
+    file_path: *const u8,
+    file_path_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    os_flavor: c_int,
+) -> c_int {
+    // Returns an integer value, representing a boolean (1 = true, 0 = false, 2 = error)
+    panic::catch_unwind(|| {
+        // Check if the pointers are null
+        if file_path.is_null() || userinput.is_null() {
+            return 2;
+        }
+
+        if file_path_len == 0 || userinput_len == 0 {
+            return 2;
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let file_path_bytes = unsafe { std::slice::from_raw_parts(file_path, file_path_len) };
+        let file_path_str = match str::from_utf8(file_path_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let userinput_str = match str::from_utf8(userinput_bytes) {
+            Ok(s) => s,
+            Err(_) => return 2, // Return error code if invalid UTF-8
+        };
+
+        let detection_results = detect_path_traversal_str(file_path_str, userinput_str, os_flavor);
+        if let PathTraversalReason::FailedToDecodeUserInput = detection_results.reason {
+            // make a special exception for failing to decode the user input (report code 3)
+            return 3;
+        }
+        if detection_results.detected {
             return 1;
         }
 
@@ -140,6 +523,9 @@ pub unsafe extern "C" fn wasm_free(ptr: *mut u8, size: usize) {
     dealloc(ptr, layout)
 }
 
+/// Returns `{"error":"Unknown SQL dialect: <dialect>"}` if `dialect` is not one of the numbers
+/// of `SqlDialect`.
+///
 /// # Safety
 ///
 /// `query` must be null or point to an initialized buffer of at least
@@ -151,6 +537,10 @@ pub unsafe extern "C" fn idor_analyze_sql_ffi(
     dialect: c_int,
 ) -> *mut c_char {
     let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
         if query.is_null() || query_len == 0 {
             return CString::new(r#"{"error":"Invalid query pointer or length"}"#)
                 .unwrap()
@@ -188,7 +578,736 @@ pub unsafe extern "C" fn idor_analyze_sql_ffi(
 
 /// # Safety
 ///
-/// `ptr` must be null or a pointer previously returned by `idor_analyze_sql_ffi`.
+/// `url` must be null or point to an initialized buffer of at least
+/// `url_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn ssrf_analyze_url_ffi(url: *const u8, url_len: usize) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if url.is_null() || url_len == 0 {
+            return CString::new(r#"{"error":"Invalid URL pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let url_bytes = unsafe { std::slice::from_raw_parts(url, url_len) };
+        let url_str = match str::from_utf8(url_bytes) {
+            Ok(s) => s,
+            Err(_) => {
+                return CString::new(r#"{"error":"Invalid UTF-8 in URL"}"#)
+                    .unwrap()
+                    .into_raw();
+            }
+        };
+
+        let json = match ssrf_analyze_url(url_str) {
+            Ok(result) => {
+                serde_json::to_string(&result).unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e))
+            }
+            Err(e) => format!(r#"{{"error":"{}"}}"#, e),
+        };
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `idor_analyze_sql_ffi`, but the dialect is passed by name (e.g. `postgres` or `mysql`).
+/// Returns `{"error":"Unknown SQL dialect: ..."}` if the dialect is unknown.
+///
+/// # Safety
+///
+/// `query` and `dialect` must each be null or point to an initialized buffer
+/// of at least `query_len`/`dialect_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn idor_analyze_sql_with_dialect_name_ffi(
+    query: *const u8,
+    query_len: usize,
+    dialect: *const u8,
+    dialect_len: usize,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if query.is_null() || query_len == 0 || dialect.is_null() || dialect_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or dialect pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let dialect_bytes = unsafe { std::slice::from_raw_parts(dialect, dialect_len) };
+        let (query_str, dialect_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(dialect_bytes)) {
+                (Ok(query_str), Ok(dialect_str)) => (query_str, dialect_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or dialect"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let json = match dialect_str
+            .parse::<SqlDialect>()
+            .and_then(|dialect| idor_analyze_sql(query_str, dialect as i32))
+        {
+            Ok(results) => serde_json::to_string(&results)
+                .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e)),
+            Err(e) => serde_json::json!({ "error": e }).to_string(),
+        };
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// # Safety
+///
+/// `filter` and `userinput` must each be null or point to an initialized buffer
+/// of at least `filter_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_nosql_injection_ffi(
+    filter: *const u8,
+    filter_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if filter.is_null() || filter_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid filter or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let filter_bytes = unsafe { std::slice::from_raw_parts(filter, filter_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (filter_str, userinput_str) = match (
+            str::from_utf8(filter_bytes),
+            str::from_utf8(userinput_bytes),
+        ) {
+            (Ok(filter_str), Ok(userinput_str)) => (filter_str, userinput_str),
+            _ => {
+                return CString::new(r#"{"error":"Invalid UTF-8 in filter or user input"}"#)
+                    .unwrap()
+                    .into_raw();
+            }
+        };
+
+        let json = match detect_nosql_injection_str(filter_str, userinput_str) {
+            Ok(result) => {
+                serde_json::to_string(&result).unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e))
+            }
+            Err(e) => serde_json::json!({ "error": e }).to_string(),
+        };
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_sql_injection`, but returns the full detection result as a JSON C string,
+/// e.g. `{"detected":true,"reason":"TokensHaveDelta","span":{"start":31,"end":43}}`
+/// or `{"error":"Unknown SQL dialect: <dialect>"}` if the dialect is unknown.
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_details_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results = detect_sql_injection_str(query_str, userinput_str, dialect);
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_sql_injection_details_ffi`, but if the user input is not found in the query,
+/// the decodings of the user input enabled in the `decodings` bitmask are tried as well (see
+/// `select_decodings_based_on_bitmask`). The JSON contains the `decoding` that was found.
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_with_decodings_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+    decodings: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results = detect_sql_injection_with_decodings(
+            query_str,
+            userinput_str,
+            dialect,
+            &select_decodings_based_on_bitmask(decodings),
+        );
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_sql_injection_details_ffi`, but every occurrence of the user input in the
+/// query is checked on its own. The `span` is the occurrence that altered the query.
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_per_occurrence_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results =
+            detect_sql_injection_per_occurrence(query_str, userinput_str, dialect);
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_sql_injection_details_ffi`, but if the query is longer than `max_len` bytes
+/// only the statements around the user input are checked. If one of those is longer than
+/// `max_len` bytes as well, the reason is `QueryTooLarge`.
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_with_max_len_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+    max_len: usize,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results =
+            detect_sql_injection_with_max_len(query_str, userinput_str, dialect, max_len);
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_sql_injection_details_ffi`, but for when the dialect is not known : the query
+/// is checked with several dialects. The result contains the `dialect` that detected the
+/// injection and the `dialects_evaluated`.
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_auto_dialect_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results = detect_sql_injection_auto_dialect(query_str, userinput_str);
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Returns a JSON C string with the syntactic context of every occurrence of the user input in
+/// the query, e.g. `[{"span":{"start":31,"end":39},"context":"Raw"}]`, or
+/// `{"error":"Failed to tokenize query"}`.
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn get_sql_user_input_contexts_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let json = match get_user_input_contexts(query_str, userinput_str, dialect) {
+            Ok(occurrences) => serde_json::to_string(&occurrences)
+                .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e)),
+            Err(e) => serde_json::json!({ "error": e }).to_string(),
+        };
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_js_injection`, but returns the full detection result as a JSON C string,
+/// e.g. `{"detected":true,"reason":"CommentsChanged"}`
+///
+/// # Safety
+///
+/// `code` and `userinput` must each be null or point to an initialized buffer
+/// of at least `code_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_js_injection_details_ffi(
+    code: *const u8,
+    code_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    sourcetype: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if code.is_null() || code_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid code or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let code_bytes = unsafe { std::slice::from_raw_parts(code, code_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (code_str, userinput_str) =
+            match (str::from_utf8(code_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(code_str), Ok(userinput_str)) => (code_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in code or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results = detect_js_injection_str(code_str, userinput_str, sourcetype);
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_js_injection_details_ffi`, but every occurrence of the user input in the code
+/// is checked on its own. The `span` is the occurrence that altered the code.
+///
+/// # Safety
+///
+/// `code` and `userinput` must each be null or point to an initialized buffer
+/// of at least `code_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_js_injection_per_occurrence_ffi(
+    code: *const u8,
+    code_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    sourcetype: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if code.is_null() || code_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid code or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let code_bytes = unsafe { std::slice::from_raw_parts(code, code_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (code_str, userinput_str) =
+            match (str::from_utf8(code_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(code_str), Ok(userinput_str)) => (code_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in code or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let detection_results =
+            detect_js_injection_per_occurrence(code_str, userinput_str, sourcetype);
+        let json = serde_json::to_string(&detection_results)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Same as `detect_sql_injection_details_ffi`, but when an injection is detected the JSON also
+/// contains a `token_diff` with the tokens of the original and the safe-replaced query around
+/// the first difference, the first differing token and the changed comment (if any).
+///
+/// # Safety
+///
+/// `query` and `userinput` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinput_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn explain_sql_injection_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinput: *const u8,
+    userinput_len: usize,
+    dialect: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinput.is_null() || userinput_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user input pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinput_bytes = unsafe { std::slice::from_raw_parts(userinput, userinput_len) };
+        let (query_str, userinput_str) =
+            match (str::from_utf8(query_bytes), str::from_utf8(userinput_bytes)) {
+                (Ok(query_str), Ok(userinput_str)) => (query_str, userinput_str),
+                _ => {
+                    return CString::new(r#"{"error":"Invalid UTF-8 in query or user input"}"#)
+                        .unwrap()
+                        .into_raw();
+                }
+            };
+
+        let explanation = explain_sql_injection_str(query_str, userinput_str, dialect);
+        let json = serde_json::to_string(&explanation)
+            .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e));
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// Checks a JSON array of user inputs against the same query, tokenizing the query only once.
+/// Returns a JSON array with a detection result per user input (see
+/// `detect_sql_injection_details_ffi`), in the same order as the user inputs.
+///
+/// # Safety
+///
+/// `query` and `userinputs` must each be null or point to an initialized buffer
+/// of at least `query_len`/`userinputs_len` bytes that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn detect_sql_injection_batch_ffi(
+    query: *const u8,
+    query_len: usize,
+    userinputs: *const u8,
+    userinputs_len: usize,
+    dialect: c_int,
+) -> *mut c_char {
+    let result = panic::catch_unwind(|| {
+        if let Err(e) = validate_dialect(dialect) {
+            return error_json(e);
+        }
+
+        if query.is_null() || query_len == 0 || userinputs.is_null() || userinputs_len == 0 {
+            return CString::new(r#"{"error":"Invalid query or user inputs pointer or length"}"#)
+                .unwrap()
+                .into_raw();
+        }
+
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let query_bytes = unsafe { std::slice::from_raw_parts(query, query_len) };
+        // SAFETY: caller guarantees a valid buffer, per the `# Safety` contract.
+        let userinputs_bytes = unsafe { std::slice::from_raw_parts(userinputs, userinputs_len) };
+        let (query_str, userinputs_str) = match (
+            str::from_utf8(query_bytes),
+            str::from_utf8(userinputs_bytes),
+        ) {
+            (Ok(query_str), Ok(userinputs_str)) => (query_str, userinputs_str),
+            _ => {
+                return CString::new(r#"{"error":"Invalid UTF-8 in query or user inputs"}"#)
+                    .unwrap()
+                    .into_raw();
+            }
+        };
+
+        let json = match serde_json::from_str::<Vec<String>>(userinputs_str) {
+            Ok(userinputs) => {
+                let userinputs: Vec<&str> = userinputs.iter().map(String::as_str).collect();
+                let detection_results = detect_sql_injection_batch(query_str, &userinputs, dialect);
+                serde_json::to_string(&detection_results)
+                    .unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e))
+            }
+            Err(e) => {
+                serde_json::json!({ "error": format!("Invalid user inputs: {}", e) }).to_string()
+            }
+        };
+
+        CString::new(json)
+            .unwrap_or_else(|_| CString::new(r#"{"error":"Failed to create C string"}"#).unwrap())
+            .into_raw()
+    });
+
+    result.unwrap_or_else(|_| {
+        CString::new(r#"{"error":"Internal error"}"#)
+            .unwrap()
+            .into_raw()
+    })
+}
+
+/// # Safety
+///
+/// `ptr` must be null or a pointer previously returned by one of the `*_ffi` functions
+/// (e.g. `idor_analyze_sql_ffi` or `detect_sql_injection_details_ffi`).
 /// It must not have been freed already, and must not be used after this call.
 #[no_mangle]
 pub unsafe extern "C" fn free_string(ptr: *mut c_char) {
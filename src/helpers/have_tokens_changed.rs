@@ -0,0 +1,68 @@
+use crate::diff_in_vec_len;
+use std::mem::discriminant;
+
+/* The tokens of a tokenizer that detects injections by replacing the user input with a safe
+ * string and tokenizing again (e.g. shell commands and templates). Tokens of the same type are
+ * compared with `is_altered`, the tokens where the user input ends up (words, strings, text, ...)
+ * are allowed to change, while e.g. operators and delimiters must stay exactly the same.
+ */
+pub trait StructuralToken {
+    // Returns true if the token changed the structure, compared to a token of the same type.
+    fn is_altered(&self, other: &Self) -> bool;
+}
+
+/*
+ * Compares the tokens of the original input with the tokens of the input where the
+ * user input was replaced by a safe string. Makes these checks :
+ * - Makes sure the amount of tokens remains the same
+ * - Makes sure the type of every token remains the same
+ * - Makes sure no token of the same type was altered (see StructuralToken)
+ */
+pub fn have_tokens_changed<T: StructuralToken>(tokens1: &[T], tokens2: &[T]) -> bool {
+    if diff_in_vec_len!(tokens1, tokens2) {
+        return true;
+    }
+
+    tokens1.iter().zip(tokens2.iter()).any(|(token1, token2)| {
+        discriminant(token1) != discriminant(token2) || token1.is_altered(token2)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::have_tokens_changed::{have_tokens_changed, StructuralToken};
+
+    enum Token {
+        Word,
+        Operator(&'static str),
+    }
+
+    impl StructuralToken for Token {
+        fn is_altered(&self, other: &Self) -> bool {
+            match (self, other) {
+                (Token::Operator(operator1), Token::Operator(operator2)) => operator1 != operator2,
+                _ => false,
+            }
+        }
+    }
+
+    #[test]
+    fn test_have_tokens_changed() {
+        use Token::{Operator, Word};
+
+        assert!(!have_tokens_changed::<Token>(&[], &[]));
+        assert!(!have_tokens_changed(
+            &[Word, Operator(";"), Word],
+            &[Word, Operator(";"), Word]
+        ));
+        assert!(have_tokens_changed(
+            &[Word, Operator(";"), Word],
+            &[Word, Operator("&&"), Word]
+        ));
+        assert!(have_tokens_changed(
+            &[Word, Operator(";"), Word],
+            &[Word, Word]
+        ));
+        assert!(have_tokens_changed(&[Word], &[Operator(";")]));
+    }
+}
@@ -0,0 +1,91 @@
+// Named character references that are commonly produced by HTML escaping functions.
+const NAMED_ENTITIES: [(&str, char); 6] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+];
+
+/* Decodes HTML character references (e.g. `&#39; OR 1=1 --` -> `' OR 1=1 --`).
+ * Supports the common named references (`&amp;`, `&lt;`, `&gt;`, `&quot;`, `&apos;`, `&nbsp;`)
+ * and decimal (`&#39;`) and hexadecimal (`&#x27;`) numeric references.
+ * References that are unknown or invalid (e.g. `&foo;` or `&#xzz;`) are kept as-is.
+ */
+pub fn html_entity_decode(input: &str) -> String {
+    if !input.contains('&') {
+        return input.to_string();
+    }
+
+    let mut decoded = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded_char = rest
+            .find(';')
+            .and_then(|end| decode_reference(&rest[1..end]).map(|c| (c, end)));
+        match decoded_char {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+// Decodes the part between `&` and `;`, e.g. `amp`, `#39` or `#x27`.
+fn decode_reference(reference: &str) -> Option<char> {
+    let code_point = if let Some(hex) = reference
+        .strip_prefix("#x")
+        .or_else(|| reference.strip_prefix("#X"))
+    {
+        u32::from_str_radix(hex, 16).ok()?
+    } else if let Some(decimal) = reference.strip_prefix('#') {
+        decimal.parse::<u32>().ok()?
+    } else {
+        return NAMED_ENTITIES
+            .iter()
+            .find(|(name, _)| *name == reference)
+            .map(|(_, c)| *c);
+    };
+
+    char::from_u32(code_point)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::html_entity_decode::html_entity_decode;
+
+    #[test]
+    fn test_html_entity_decode() {
+        assert_eq!(html_entity_decode("hello"), "hello");
+        assert_eq!(html_entity_decode("&#39; OR 1=1 --"), "' OR 1=1 --");
+        assert_eq!(html_entity_decode("&#x27;&#X27;"), "''");
+        assert_eq!(
+            html_entity_decode("&quot;a&quot; &amp; &lt;b&gt;"),
+            "\"a\" & <b>"
+        );
+        assert_eq!(html_entity_decode("&apos;&nbsp;"), "'\u{a0}");
+        assert_eq!(html_entity_decode("&amp;amp;"), "&amp;");
+    }
+
+    #[test]
+    fn test_html_entity_decode_invalid_references() {
+        assert_eq!(html_entity_decode("a & b"), "a & b");
+        assert_eq!(html_entity_decode("&foo;"), "&foo;");
+        assert_eq!(html_entity_decode("&#xzz;"), "&#xzz;");
+        assert_eq!(html_entity_decode("&#1114112;"), "&#1114112;");
+        assert_eq!(html_entity_decode("&amp"), "&amp");
+        assert_eq!(html_entity_decode("&&#39;"), "&'");
+    }
+}
@@ -1 +1,4 @@
 pub mod diff_in_vec_len;
+pub mod have_tokens_changed;
+pub mod html_entity_decode;
+pub mod percent_decode;
@@ -0,0 +1,58 @@
+/* Decodes percent-encoded sequences (e.g. `%2e%2e%2f` -> `../`).
+ * Sequences that are not valid percent-encoding (e.g. `%zz` or a trailing `%`) are kept as-is.
+ * Returns None if the decoded bytes are not valid UTF-8.
+ */
+pub fn percent_decode(input: &str) -> Option<String> {
+    if !input.contains('%') {
+        return Some(input.to_string());
+    }
+
+    let bytes = input.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(high), Some(low)) = (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                decoded.push((high << 4) | low);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::percent_decode::percent_decode;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("hello"), Some("hello".to_string()));
+        assert_eq!(percent_decode("%2e%2e%2f"), Some("../".to_string()));
+        assert_eq!(percent_decode("%2E%2E%5C"), Some("..\\".to_string()));
+        assert_eq!(percent_decode("a%20b"), Some("a b".to_string()));
+        assert_eq!(percent_decode("%C3%A9"), Some("é".to_string()));
+        assert_eq!(percent_decode("%252e"), Some("%2e".to_string()));
+    }
+
+    #[test]
+    fn test_percent_decode_invalid_sequences() {
+        assert_eq!(percent_decode("100%"), Some("100%".to_string()));
+        assert_eq!(percent_decode("%2"), Some("%2".to_string()));
+        assert_eq!(percent_decode("%zz"), Some("%zz".to_string()));
+        assert_eq!(percent_decode("%C3"), None);
+    }
+}
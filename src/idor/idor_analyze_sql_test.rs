@@ -5182,6 +5182,8 @@ mod tests {
             ("MySQL", 8),
             ("Postgres", 9),
             ("SQLite", 12),
+            ("Oracle", 13),
+            ("MariaDB", 14),
         ] {
             let result = idor_analyze_sql(
                 "SELECT id, (SELECT count(*) FROM jsonb_array_elements(tags) AS elem) AS n FROM assets WHERE tenant_id = 1",
@@ -5226,6 +5228,8 @@ mod tests {
             ("MySQL", 8),
             ("Postgres", 9),
             ("SQLite", 12),
+            ("Oracle", 13),
+            ("MariaDB", 14),
         ] {
             let result =
                 idor_analyze_sql("SELECT * FROM users (NOLOCK) WHERE tenant_id = 1", dialect)
@@ -7323,4 +7327,61 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn test_oracle_bind_variables_and_quote_delimited_strings() {
+        assert_eq!(
+            idor_analyze_sql(
+                "SELECT * FROM users WHERE tenant_id = :1 AND name = q'[O'Brien]'",
+                13,
+            )
+            .unwrap(),
+            vec![SqlQueryResult {
+                kind: "select".into(),
+                tables: vec![TableRef {
+                    name: "users".into(),
+                    alias: None,
+                }],
+                filters: vec![
+                    FilterColumn {
+                        table: None,
+                        column: "tenant_id".into(),
+                        value: ":1".into(),
+                        placeholder_number: None,
+                        is_placeholder: true,
+                    },
+                    FilterColumn {
+                        table: None,
+                        column: "name".into(),
+                        value: "O'Brien".into(),
+                        placeholder_number: None,
+                        is_placeholder: false,
+                    },
+                ],
+                insert_columns: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_mariadb_placeholder() {
+        assert_eq!(
+            idor_analyze_sql("SELECT * FROM users WHERE tenant_id = ?", 14).unwrap(),
+            vec![SqlQueryResult {
+                kind: "select".into(),
+                tables: vec![TableRef {
+                    name: "users".into(),
+                    alias: None,
+                }],
+                filters: vec![FilterColumn {
+                    table: None,
+                    column: "tenant_id".into(),
+                    value: "?".into(),
+                    placeholder_number: Some(0),
+                    is_placeholder: true,
+                }],
+                insert_columns: None,
+            }]
+        );
+    }
 }
@@ -1,83 +1,223 @@
+use super::find_first_changed_node::{find_first_changed_node, AstNodeDifference};
 use super::have_comments_changed::have_comments_changed;
-use super::have_statements_changed::have_statements_changed;
 use super::helpers::select_sourcetype_based_on_enum::select_sourcetype_based_on_enum;
 use super::is_safe_js_input::is_safe_js_input;
 use oxc::allocator::Allocator;
+use oxc::ast::ast::Program;
 use oxc::parser::{ParseOptions, Parser};
 use oxc::span::SourceType;
+use serde::Serialize;
+use std::ops::Range;
 
-pub fn detect_js_injection_str(code: &str, userinput: &str, sourcetype: i32) -> bool {
+#[derive(Debug, Serialize)]
+pub struct JsInjectionDetectionResult {
+    pub detected: bool,
+    pub reason: DetectionReason,
+    // The first node of the code that changed (when the reason is TemplateLiteralBreakout or
+    // StatementsChanged).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_node: Option<AstNodeDifference>,
+    // Byte offsets of the occurrence of the user input in the code that altered it, or of the
+    // first occurrence if none did (only set by detect_js_injection_per_occurrence).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Range<usize>>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum DetectionReason {
+    // not an injection
+    UserInputTooSmall,
+    UserInputLongerThanCode,
+    UserInputNotInCode,
+    SafeJsInput,
+    FailedToParseCode,
+    NoChangesFound,
+    // injection
+    CommentsChanged,
+    TemplateLiteralBreakout,
+    StatementsChanged,
+}
+
+impl From<DetectionReason> for JsInjectionDetectionResult {
+    fn from(reason: DetectionReason) -> Self {
+        JsInjectionDetectionResult {
+            detected: matches!(
+                reason,
+                DetectionReason::CommentsChanged
+                    | DetectionReason::TemplateLiteralBreakout
+                    | DetectionReason::StatementsChanged
+            ),
+            reason,
+            changed_node: None,
+            span: None,
+        }
+    }
+}
+
+pub fn detect_js_injection_str(
+    code: &str,
+    userinput: &str,
+    sourcetype: i32,
+) -> JsInjectionDetectionResult {
+    let allocator = Allocator::default();
+    let source_type: SourceType = select_sourcetype_based_on_enum(sourcetype);
+
+    let program = match parse_code_containing_user_input(&allocator, code, userinput, source_type) {
+        Ok(program) => program,
+        Err(reason) => return reason.into(),
+    };
+
+    has_structure_changed(
+        &allocator,
+        &program,
+        |replacement| code.replace(userinput, replacement),
+        userinput.len(),
+        source_type,
+    )
+    .unwrap_or_else(|| DetectionReason::NoChangesFound.into())
+}
+
+/* Same as detect_js_injection_str, but when the user input occurs more than once in the code,
+ * every occurrence is replaced and checked on its own. The `span` of the result is the
+ * occurrence that altered the structure of the code, e.g. for
+ * `const a = "1; alert(1)"; const b = 1; alert(1)` with user input `1; alert(1)` only the
+ * second occurrence (35..46) is an injection.
+ */
+pub fn detect_js_injection_per_occurrence(
+    code: &str,
+    userinput: &str,
+    sourcetype: i32,
+) -> JsInjectionDetectionResult {
+    let allocator = Allocator::default();
+    let source_type: SourceType = select_sourcetype_based_on_enum(sourcetype);
+    let mut occurrences = code
+        .match_indices(userinput)
+        .map(|(start, _)| start..start + userinput.len());
+    let first_occurrence = occurrences.clone().next();
+
+    let program = match parse_code_containing_user_input(&allocator, code, userinput, source_type) {
+        Ok(program) => program,
+        Err(reason) => {
+            return JsInjectionDetectionResult {
+                span: first_occurrence,
+                ..reason.into()
+            }
+        }
+    };
+
+    let changed = occurrences.find_map(|occurrence| {
+        let result = has_structure_changed(
+            &allocator,
+            &program,
+            |replacement| {
+                let mut code_without_input = code.to_string();
+                code_without_input.replace_range(occurrence.clone(), replacement);
+                code_without_input
+            },
+            userinput.len(),
+            source_type,
+        )?;
+        Some(JsInjectionDetectionResult {
+            span: Some(occurrence),
+            ..result
+        })
+    });
+
+    changed.unwrap_or_else(|| JsInjectionDetectionResult {
+        span: first_occurrence,
+        ..DetectionReason::NoChangesFound.into()
+    })
+}
+
+/* Parses the code, unless the user input can't be an injection (too small, not in the code,
+ * a safe input like math, ...) or the code can't be parsed. The error is the reason why it's not
+ * an injection.
+ */
+fn parse_code_containing_user_input<'a>(
+    allocator: &'a Allocator,
+    code: &'a str,
+    userinput: &str,
+    source_type: SourceType,
+) -> Result<Program<'a>, DetectionReason> {
     if userinput.len() <= 1 {
         // We assume that a single character cannot be an injection.
-        return false;
+        return Err(DetectionReason::UserInputTooSmall);
     }
 
     if userinput.len() > code.len() {
         // If the user input is longer than the code, it's not an injection.
-        return false;
+        return Err(DetectionReason::UserInputLongerThanCode);
     }
 
     if !code.contains(userinput) {
         // If the query does not contain the user input, it's not an injection.
-        return false;
+        return Err(DetectionReason::UserInputNotInCode);
     }
 
-    let allocator = Allocator::default();
-    let source_type: SourceType = select_sourcetype_based_on_enum(sourcetype);
-
-    if is_safe_js_input(userinput, &allocator, source_type) {
+    if is_safe_js_input(userinput, allocator, source_type) {
         // Ignore some non dangerous inputs, e.g. math
-        return false;
+        return Err(DetectionReason::SafeJsInput);
     }
 
-    let parser_result = Parser::new(&allocator, code, source_type)
-        .with_options(ParseOptions {
-            allow_return_outside_function: true,
-            ..ParseOptions::default()
-        })
-        .parse();
+    parse_code(allocator, code, source_type).ok_or(DetectionReason::FailedToParseCode)
+}
 
-    if parser_result.panicked || !parser_result.errors.is_empty() {
-        return false;
+/* Replaces the user input (using `replace_user_input`) with a safe string of equal length and
+ * checks if the comments, template literals or statements of the code changed. Returns the result
+ * if it's an injection.
+ */
+fn has_structure_changed(
+    allocator: &Allocator,
+    program: &Program,
+    replace_user_input: impl Fn(&str) -> String,
+    userinput_len: usize,
+    source_type: SourceType,
+) -> Option<JsInjectionDetectionResult> {
+    let safe_replace_str = "a".repeat(userinput_len);
+    let code_without_input = replace_user_input(&safe_replace_str);
+    let mut program_without_input = parse_code(allocator, &code_without_input, source_type);
+
+    // Try to parse by replacing the user input with a empty string.
+    let code_without_input_empty;
+    if program_without_input.is_none() {
+        code_without_input_empty = replace_user_input("");
+        program_without_input = parse_code(allocator, &code_without_input_empty, source_type);
+    }
+
+    let program_without_input = program_without_input?;
+
+    if have_comments_changed(&program.comments, &program_without_input.comments) {
+        // If the number of comments is different, it's an injection.
+        return Some(DetectionReason::CommentsChanged.into());
     }
 
-    let safe_replace_str = "a".repeat(userinput.len());
-    let mut code_without_input: String = code.replace(userinput, &safe_replace_str);
+    // The user input broke out of a template literal or altered the statements of the code.
+    find_first_changed_node(program, &program_without_input).map(|(reason, changed_node)| {
+        JsInjectionDetectionResult {
+            detected: true,
+            reason,
+            changed_node: Some(changed_node),
+            span: None,
+        }
+    })
+}
 
-    let mut parser_result_without_input = Parser::new(&allocator, &code_without_input, source_type)
+// Returns `None` if the parser panicked or reported errors.
+fn parse_code<'a>(
+    allocator: &'a Allocator,
+    code: &'a str,
+    source_type: SourceType,
+) -> Option<Program<'a>> {
+    let parser_result = Parser::new(allocator, code, source_type)
         .with_options(ParseOptions {
             allow_return_outside_function: true,
             ..ParseOptions::default()
         })
         .parse();
 
-    if parser_result_without_input.panicked || !parser_result_without_input.errors.is_empty() {
-        // Try to parse by replacing the user input with a empty string.
-        code_without_input = code.replace(userinput, "");
-
-        parser_result_without_input = Parser::new(&allocator, &code_without_input, source_type)
-            .with_options(ParseOptions {
-                allow_return_outside_function: true,
-                ..ParseOptions::default()
-            })
-            .parse();
-
-        if parser_result_without_input.panicked || !parser_result_without_input.errors.is_empty() {
-            return false;
-        }
-    }
-
-    if have_comments_changed(
-        &parser_result.program.comments,
-        &parser_result_without_input.program.comments,
-    ) {
-        // If the number of comments is different, it's an injection.
-        return true;
-    }
-
-    if have_statements_changed(&parser_result.program, &parser_result_without_input.program) {
-        return true;
+    if parser_result.panicked || !parser_result.errors.is_empty() {
+        return None;
     }
 
-    false
+    Some(parser_result.program)
 }
@@ -1,24 +1,29 @@
 #[cfg(test)]
 mod tests {
-    use crate::js_injection::detect_js_injection::detect_js_injection_str;
+    use crate::js_injection::detect_js_injection::{
+        detect_js_injection_per_occurrence, detect_js_injection_str, DetectionReason,
+    };
+    use crate::js_injection::find_first_changed_node::AstNodeDifference;
 
     macro_rules! is_injection {
         ($code:expr, $input:expr, $sourcetype:expr) => {
-            assert!(detect_js_injection_str(
-                &$code.to_lowercase(),
-                &$input.to_lowercase(),
-                $sourcetype
-            ))
+            assert!(
+                detect_js_injection_str(&$code.to_lowercase(), &$input.to_lowercase(), $sourcetype)
+                    .detected
+            )
         };
     }
 
     macro_rules! not_injection {
         ($code:expr, $input:expr, $sourcetype:expr) => {
-            assert!(!detect_js_injection_str(
-                &$code.to_lowercase(),
-                &$input.to_lowercase(),
-                $sourcetype
-            ))
+            assert!(
+                !detect_js_injection_str(
+                    &$code.to_lowercase(),
+                    &$input.to_lowercase(),
+                    $sourcetype
+                )
+                .detected
+            )
         };
     }
 
@@ -302,4 +307,221 @@ mod tests {
             3
         );
     }
+
+    #[test]
+    fn test_per_occurrence_reports_offending_occurrence() {
+        let code = "const a = \"1; alert(1)\"; const b = 1; alert(1)";
+        let result = detect_js_injection_per_occurrence(code, "1; alert(1)", 0);
+        assert!(result.detected);
+        assert_eq!(result.reason, DetectionReason::StatementsChanged);
+        assert_eq!(result.span, Some(35..46));
+        assert_eq!(&code[35..46], "1; alert(1)");
+
+        let code = "const test = 'Hello World!'; //'; const test2 = 'Hello World!'; //';";
+        let result = detect_js_injection_per_occurrence(code, "Hello World!'; //", 0);
+        assert!(result.detected);
+        assert_eq!(result.reason, DetectionReason::CommentsChanged);
+        assert_eq!(result.span, Some(14..31));
+    }
+
+    #[test]
+    fn test_per_occurrence_without_injection() {
+        let result = detect_js_injection_per_occurrence(
+            "const a = 'Hello World!'; const b = 'Hello World!';",
+            "Hello World!",
+            0,
+        );
+        assert!(!result.detected);
+        assert_eq!(result.reason, DetectionReason::NoChangesFound);
+        assert_eq!(result.span, Some(11..23));
+
+        let result = detect_js_injection_per_occurrence("const a = 1 + 1;", "1 + 1", 0);
+        assert!(!result.detected);
+        assert_eq!(result.reason, DetectionReason::SafeJsInput);
+        assert_eq!(result.span, Some(10..15));
+
+        let result = detect_js_injection_per_occurrence("const a = 'b';", "not in code", 0);
+        assert!(!result.detected);
+        assert_eq!(result.reason, DetectionReason::UserInputNotInCode);
+        assert_eq!(result.span, None);
+    }
+
+    #[test]
+    fn test_detection_reasons() {
+        let reason =
+            |code: &str, userinput: &str| detect_js_injection_str(code, userinput, 0).reason;
+
+        assert_eq!(
+            reason("const test = 'Hello World!'; //';", "Hello World!'; //"),
+            DetectionReason::CommentsChanged
+        );
+        assert_eq!(
+            reason("const a = 1; alert(1)", "1; alert(1)"),
+            DetectionReason::StatementsChanged
+        );
+        assert_eq!(
+            reason("const test = 'Hello World!';", "Hello World!"),
+            DetectionReason::NoChangesFound
+        );
+        assert_eq!(
+            reason("const test = 'a';", "a"),
+            DetectionReason::UserInputTooSmall
+        );
+        assert_eq!(
+            reason("const a = 1;", "const a = 1; const b = 2;"),
+            DetectionReason::UserInputLongerThanCode
+        );
+        assert_eq!(
+            reason("const test = 'Hello World!';", "Goodbye"),
+            DetectionReason::UserInputNotInCode
+        );
+        assert_eq!(
+            reason("const test = 1 + 2;", "1 + 2"),
+            DetectionReason::SafeJsInput
+        );
+        assert_eq!(
+            reason("const user = {\"name\": \"John\"};", "{\"name\": \"John\"}"),
+            DetectionReason::SafeJsInput
+        );
+        assert_eq!(
+            reason("const test = 'Hello World!", "Hello World!"),
+            DetectionReason::FailedToParseCode
+        );
+    }
+
+    #[test]
+    fn test_node_kinds_swapped_with_same_amount_of_nodes() {
+        // `obj.#secret` has as many nodes as `obj.aaaaaaa`, but it accesses a private field.
+        let code = "class A { #secret = 1; get(obj) { return obj.#secret; } }";
+        is_injection!(code, "#secret", 0);
+
+        let result = detect_js_injection_str(code, "#secret", 0);
+        assert_eq!(result.reason, DetectionReason::StatementsChanged);
+        assert_eq!(
+            result.changed_node,
+            Some(AstNodeDifference {
+                index: 5,
+                original: Some("PrivateIdentifier".to_string()),
+                safe: Some("IdentifierName".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_literals_swapped_with_same_amount_of_nodes() {
+        // `[]` and `{}` are a single node, just like the identifier `aa` that replaces them, but
+        // they are safe literals.
+        not_injection!("if ([]) { return true; }", "[]", 0);
+        not_injection!("if ({}) { return true; }", "{}", 0);
+        not_injection!("return isAdmin === [];", "[]", 0);
+        not_injection!("const role = {}; login(role);", "{}", 0);
+        not_injection!("Object.assign(config, {});", "{}", 1);
+    }
+
+    #[test]
+    fn test_string_literals_are_not_safe_input() {
+        // The string `"a/;alert(1);/"` closes the regex it lands in.
+        is_injection!("const x = /\"a/;alert(1);/\"/;", "\"a/;alert(1);/\"", 0);
+        is_injection!("const name = ''; alert(1); '';", "'; alert(1); '", 0);
+        not_injection!("const greeting = \"hello\";", "\"hello\"", 0);
+    }
+
+    #[test]
+    fn test_literals_replaced_by_identifiers() {
+        not_injection!("if (true) { return true; }", "true", 0);
+        not_injection!("const user = null;", "null", 0);
+        not_injection!("const x = this;", "this", 0);
+        not_injection!("const x = 123n;", "123n", 0);
+        not_injection!("const x = /abc/;", "/abc/", 0);
+        not_injection!("const x = { 123: 'a' };", "123", 0);
+        not_injection!("'use strict'; 'hello';", "'hello'", 0);
+        not_injection!("const x = `Hello ${name}!`;", "Hello", 0);
+        not_injection!("const x: string = 'Hello World!';", "Hello World!", 1);
+    }
+
+    #[test]
+    fn test_template_literal_breakout() {
+        is_injection!(
+            "const msg = `Hello ${name}, `; alert(1); `!`;",
+            "`; alert(1); `",
+            0
+        );
+        is_injection!(
+            "const msg = `Hello ` + alert(1) + `!`;",
+            "` + alert(1) + `",
+            0
+        );
+        is_injection!("const msg = `Hello ${alert(1)}!`;", "${alert(1)}", 0);
+        is_injection!("const msg = `Hello ${name}${alert(1)}!`;", "${alert(1)}", 0);
+        is_injection!(
+            "const msg = `Hello ${name}!`; const b = `${alert(1)}`;",
+            "${alert(1)}",
+            0
+        );
+        is_injection!(
+            "const q = sql`SELECT * FROM users WHERE id = ${process.exit()}`;",
+            "${process.exit()}",
+            0
+        );
+        is_injection!(
+            "const msg = tag`Hello `; alert`1`; `!`;",
+            "`; alert`1`; `",
+            0
+        );
+
+        let result = detect_js_injection_str("const msg = `Hello ${alert(1)}!`;", "${alert(1)}", 0);
+        assert_eq!(result.reason, DetectionReason::TemplateLiteralBreakout);
+        assert_eq!(
+            result.changed_node,
+            Some(AstNodeDifference {
+                index: 4,
+                original: Some("TemplateLiteral(2)".to_string()),
+                safe: Some("TemplateLiteral(1)".to_string()),
+            })
+        );
+        assert_eq!(
+            detect_js_injection_str("const msg = `Hello `; alert(1); `!`;", "`; alert(1); `", 0)
+                .reason,
+            DetectionReason::TemplateLiteralBreakout
+        );
+    }
+
+    #[test]
+    fn test_tagged_template_breakout() {
+        // The quasi of the tagged template is only compared once.
+        let result = detect_js_injection_str(
+            "const q = sql`a ${b}`; tag`x${alert(1)}`;",
+            "${alert(1)}",
+            0,
+        );
+        assert_eq!(result.reason, DetectionReason::TemplateLiteralBreakout);
+        assert_eq!(
+            result.changed_node,
+            Some(AstNodeDifference {
+                index: 13,
+                original: Some("TemplateLiteral(2)".to_string()),
+                safe: Some("TemplateLiteral(1)".to_string()),
+            })
+        );
+        not_injection!("const q = sql`a ${b}`; tag`x${c}`;", "x", 0);
+    }
+
+    #[test]
+    fn test_user_input_inside_template_literal() {
+        not_injection!("const msg = `Hello John Doe!`;", "John Doe", 0);
+        not_injection!("const msg = `Price: $100`;", "$100", 0);
+        not_injection!("const msg = `Hello '; alert(1); '!`;", "'; alert(1); '", 0);
+        not_injection!("const msg = `Hello {alert(1)}!`;", "{alert(1)}", 0);
+        not_injection!(
+            "const msg = `Hello ${name}, welcome back!`;",
+            "welcome back",
+            0
+        );
+        not_injection!(
+            "const q = sql`SELECT * FROM users WHERE name = ${name}`;",
+            "name =",
+            0
+        );
+        not_injection!("const msg = 'Hello `; alert(1); `!';", "`; alert(1); `", 0);
+    }
 }
@@ -0,0 +1,121 @@
+use super::detect_js_injection::DetectionReason;
+use oxc::ast::ast::Program;
+use oxc::ast::{AstKind, AstType};
+use oxc_ast_visit::Visit;
+use serde::Serialize;
+
+// The first node that differs between the original program and the program without user input.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AstNodeDifference {
+    // Index of the node in the order the AST is visited.
+    pub index: usize,
+    // `None` if the program has no more nodes. Template literals include the amount of quasis,
+    // e.g. `TemplateLiteral(2)`.
+    pub original: Option<String>,
+    pub safe: Option<String>,
+}
+
+// A node of the AST, with the amount of quasis if it's a template literal (0 otherwise).
+struct AstNode {
+    ty: AstType,
+    quasis: usize,
+}
+
+/* Compares the sequence of node types of both programs (in the order the AST is visited), so that
+ * payloads that swap nodes while keeping the amount of nodes the same are detected as well.
+ * The user input is replaced with `aaa...`, so literals, identifiers and `this` are all treated
+ * as the same type : `if (true)` becomes `if (aaaa)` without changing the structure of the code.
+ * The same goes for a directive like `'use strict';`, which becomes an expression statement.
+ *
+ * The reason is TemplateLiteralBreakout if the user input broke out of a template literal, e.g.
+ * with `` `; alert(1); ` `` or added a substitution like `${alert(1)}` to it : the template
+ * literals (and tagged templates) and the amount of quasis in each of them have to be the same.
+ * `Hello ${name}!` has two quasis (`Hello ` and `!`) and one substitution. Otherwise the reason
+ * is StatementsChanged.
+ */
+pub fn find_first_changed_node(
+    program1: &Program,
+    program2: &Program,
+) -> Option<(DetectionReason, AstNodeDifference)> {
+    let nodes1 = collect_ast_nodes(program1);
+    let nodes2 = collect_ast_nodes(program2);
+
+    let index = (0..nodes1.len().max(nodes2.len())).find(|&i| {
+        nodes1.get(i).map(|node| (normalize(&node.ty), node.quasis))
+            != nodes2.get(i).map(|node| (normalize(&node.ty), node.quasis))
+    })?;
+
+    let reason = if template_literals(&nodes1).ne(template_literals(&nodes2)) {
+        DetectionReason::TemplateLiteralBreakout
+    } else {
+        DetectionReason::StatementsChanged
+    };
+
+    Some((
+        reason,
+        AstNodeDifference {
+            index,
+            original: nodes1.get(index).map(describe),
+            safe: nodes2.get(index).map(describe),
+        },
+    ))
+}
+
+fn normalize(ty: &AstType) -> AstType {
+    match ty {
+        AstType::IdentifierName
+        | AstType::IdentifierReference
+        | AstType::ThisExpression
+        | AstType::BooleanLiteral
+        | AstType::NullLiteral
+        | AstType::NumericLiteral
+        | AstType::StringLiteral
+        | AstType::BigIntLiteral
+        | AstType::RegExpLiteral => AstType::IdentifierReference,
+        AstType::Directive => AstType::ExpressionStatement,
+        ty => *ty,
+    }
+}
+
+fn template_literals(nodes: &[AstNode]) -> impl Iterator<Item = (AstType, usize)> + '_ {
+    nodes
+        .iter()
+        .filter(|node| {
+            matches!(
+                node.ty,
+                AstType::TemplateLiteral | AstType::TaggedTemplateExpression
+            )
+        })
+        .map(|node| (node.ty, node.quasis))
+}
+
+fn describe(node: &AstNode) -> String {
+    match node.ty {
+        AstType::TemplateLiteral => format!("{:?}({})", node.ty, node.quasis),
+        ty => format!("{:?}", ty),
+    }
+}
+
+fn collect_ast_nodes(program: &Program) -> Vec<AstNode> {
+    let mut pass = ASTCollector { nodes: Vec::new() };
+    pass.visit_program(program);
+    pass.nodes
+}
+
+struct ASTCollector {
+    nodes: Vec<AstNode>,
+}
+
+impl<'a> Visit<'a> for ASTCollector {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        // The quasi of a tagged template is visited as a template literal of its own.
+        let quasis = match kind {
+            AstKind::TemplateLiteral(template) => template.quasis.len(),
+            _ => 0,
+        };
+        self.nodes.push(AstNode {
+            ty: kind.ty(),
+            quasis,
+        });
+    }
+}
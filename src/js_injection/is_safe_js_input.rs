@@ -1,3 +1,4 @@
+use super::is_safe_js_literal::is_safe_js_literal;
 use oxc::allocator::Allocator;
 use oxc::ast::ast::{BinaryOperator, UnaryOperator};
 use oxc::ast::AstKind;
@@ -20,7 +21,11 @@ const SAFE_UNARY_OPERATORS: [UnaryOperator; 2] =
     [UnaryOperator::UnaryNegation, UnaryOperator::UnaryPlus];
 
 pub fn is_safe_js_input(user_input: &str, allocator: &Allocator, source_type: SourceType) -> bool {
-    // Right now this function only returns true if the user input contains numbers
+    if is_safe_js_literal(user_input, allocator, source_type) {
+        return true;
+    }
+
+    // Other than literals, this function only returns true if the user input contains numbers
     // This is a early return to avoid parsing the user input if it doesn't contain any numbers
     if !user_input.bytes().any(|b| b.is_ascii_digit()) {
         return false;
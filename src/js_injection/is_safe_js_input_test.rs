@@ -39,6 +39,10 @@ mod tests {
         is_safe!("1 + -2", &allocator, source_type);
         is_safe!("-(1 + 2)", &allocator, source_type);
         is_safe!("- -10", &allocator, source_type);
+        // Literals (see is_safe_js_literal_test.rs)
+        is_safe!("[1, 2, 3]", &allocator, source_type);
+        is_safe!("{ x: 1, y: 2 }", &allocator, source_type);
+        is_safe!("'test'", &allocator, source_type);
     }
 
     #[test]
@@ -51,7 +55,6 @@ mod tests {
         is_unsafe!("alert('test')", &allocator, source_type);
         is_unsafe!("const x = 1", &allocator, source_type);
         is_unsafe!("test()", &allocator, source_type);
-        is_unsafe!("'test'", &allocator, source_type);
         is_unsafe!("'test' + 'test'", &allocator, source_type);
         is_unsafe!("'; //", &allocator, source_type);
         is_unsafe!("// test", &allocator, source_type);
@@ -61,7 +64,6 @@ mod tests {
         is_unsafe!("1 == true", &allocator, source_type);
         is_unsafe!("== true", &allocator, source_type);
         is_unsafe!("!!''", &allocator, source_type);
-        is_unsafe!("[1, 2, 3]", &allocator, source_type);
         is_unsafe!("({ x: 1, y: 2 })", &allocator, source_type);
         is_unsafe!("function test() { return 1; }", &allocator, source_type);
         is_unsafe!("class Test { constructor() {} }", &allocator, source_type);
@@ -0,0 +1,98 @@
+use oxc::allocator::Allocator;
+use oxc::ast::ast::{
+    ArrayExpressionElement, Expression, ObjectPropertyKind, PropertyKey, PropertyKind, Statement,
+    UnaryOperator,
+};
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+
+// The characters that can end a template literal, regex or comment around the user input.
+const BREAKOUT_CHARS: [char; 9] = ['`', '$', '\\', '*', '/', '\n', '\r', '\u{2028}', '\u{2029}'];
+
+/* Returns true if the user input is a literal : a boolean, null, a number, a string or an object or
+ * array that only contains literals, e.g. `{ "id": 1, tags: [true, null, "admin"] }`. User input
+ * that contains anything that can end a template literal, regex or comment of the code it lands in
+ * is never a safe literal, neither are strings that are not plain (see is_plain_string). These are
+ * checked by comparing the structure of the code instead.
+ */
+pub fn is_safe_js_literal(
+    user_input: &str,
+    allocator: &Allocator,
+    source_type: SourceType,
+) -> bool {
+    // Early return to avoid parsing user input that can't be a (safe) literal
+    if user_input.contains(BREAKOUT_CHARS)
+        || !user_input
+            .trim_start()
+            .starts_with(|c: char| "tfn[{-'\"".contains(c) || c.is_ascii_digit())
+    {
+        return false;
+    }
+
+    // The parentheses make sure that `{...}` is parsed as an object and not as a block
+    let wrapped = format!("({})", user_input);
+    let parser_result = Parser::new(allocator, &wrapped, source_type).parse();
+    if parser_result.panicked || !parser_result.errors.is_empty() {
+        return false;
+    }
+
+    let program = parser_result.program;
+    match program.body.as_slice() {
+        [Statement::ExpressionStatement(statement)] if program.comments.is_empty() => {
+            matches!(
+                &statement.expression,
+                Expression::ParenthesizedExpression(p) if is_literal_value(&p.expression)
+            )
+        }
+        _ => false,
+    }
+}
+
+fn is_literal_value(expression: &Expression) -> bool {
+    match expression {
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumericLiteral(_) => true,
+        Expression::StringLiteral(string) => is_plain_string(&string.value),
+        Expression::UnaryExpression(u) => {
+            u.operator == UnaryOperator::UnaryNegation
+                && matches!(u.argument, Expression::NumericLiteral(_))
+        }
+        Expression::ArrayExpression(array) => array.elements.iter().all(|element| {
+            !matches!(
+                element,
+                ArrayExpressionElement::SpreadElement(_) | ArrayExpressionElement::Elision(_)
+            ) && is_literal_value(element.to_expression())
+        }),
+        Expression::ObjectExpression(object) => object.properties.iter().all(|property| {
+            let ObjectPropertyKind::ObjectProperty(p) = property else {
+                return false;
+            };
+            p.kind == PropertyKind::Init
+                && !p.method
+                && !p.shorthand
+                && !p.computed
+                && match &p.key {
+                    PropertyKey::StaticIdentifier(_) | PropertyKey::NumericLiteral(_) => true,
+                    PropertyKey::StringLiteral(key) => is_plain_string(&key.value),
+                    _ => false,
+                }
+                && is_literal_value(&p.value)
+        }),
+        _ => false,
+    }
+}
+
+/* The strings of the user input can close a string of the code, e.g. `eval("x = '" + input + "'")`
+ * with the user input `' + alert(1) + '`, which is a single string literal. The content of such a
+ * string then ends up outside of the strings of the code, e.g. `x = '{'a': 'b c'}'`. If it only
+ * contains words (without operators like `in`), that's a syntax error instead of code that runs.
+ */
+fn is_plain_string(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ' ')
+        && !value
+            .split(' ')
+            .any(|word| word == "in" || word == "instanceof")
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use crate::js_injection::is_safe_js_literal::is_safe_js_literal;
+    use oxc::allocator::Allocator;
+    use oxc::span::SourceType;
+
+    macro_rules! is_literal {
+        ($input:expr, $allocator:expr, $sourcetype:expr) => {
+            assert!(is_safe_js_literal($input, $allocator, $sourcetype))
+        };
+    }
+
+    macro_rules! is_not_literal {
+        ($input:expr, $allocator:expr, $sourcetype:expr) => {
+            assert!(!is_safe_js_literal($input, $allocator, $sourcetype))
+        };
+    }
+
+    #[test]
+    fn test_primitive_literals() {
+        let allocator = Allocator::default();
+        let source_type: SourceType = SourceType::unambiguous();
+
+        is_literal!("true", &allocator, source_type);
+        is_literal!("false", &allocator, source_type);
+        is_literal!("null", &allocator, source_type);
+        is_literal!("10", &allocator, source_type);
+        is_literal!("-3.14", &allocator, source_type);
+        is_literal!(" true ", &allocator, source_type);
+    }
+
+    #[test]
+    fn test_json_literals() {
+        let allocator = Allocator::default();
+        let source_type: SourceType = SourceType::unambiguous();
+
+        is_literal!("[]", &allocator, source_type);
+        is_literal!("{}", &allocator, source_type);
+        is_literal!("[1, 2, 3]", &allocator, source_type);
+        is_literal!("[true, null, -1]", &allocator, source_type);
+        is_literal!("{ id: 1, 2: null }", &allocator, source_type);
+        is_literal!(
+            "{ user: { tags: [1, 2], admin: false } }",
+            &allocator,
+            source_type
+        );
+    }
+
+    #[test]
+    fn test_not_literals() {
+        let allocator = Allocator::default();
+        let source_type: SourceType = SourceType::unambiguous();
+
+        is_not_literal!("", &allocator, source_type);
+        is_not_literal!("x", &allocator, source_type);
+        is_not_literal!("undefined", &allocator, source_type);
+        is_not_literal!("1 + 2", &allocator, source_type);
+        is_not_literal!("'a' + 'b'", &allocator, source_type);
+        is_not_literal!("'use strict';", &allocator, source_type);
+        is_not_literal!("`hello`", &allocator, source_type);
+        is_not_literal!("`${alert(1)}`", &allocator, source_type);
+        is_not_literal!("/test/", &allocator, source_type);
+        is_not_literal!("10n", &allocator, source_type);
+        is_not_literal!("-x", &allocator, source_type);
+        is_not_literal!("!true", &allocator, source_type);
+        is_not_literal!("true, false", &allocator, source_type);
+        is_not_literal!("(true)", &allocator, source_type);
+        is_not_literal!("true // test", &allocator, source_type);
+        is_not_literal!("null /* test */", &allocator, source_type);
+        is_not_literal!("1), alert((1", &allocator, source_type);
+    }
+
+    #[test]
+    fn test_json_with_non_literal_values() {
+        let allocator = Allocator::default();
+        let source_type: SourceType = SourceType::unambiguous();
+
+        is_not_literal!("[x]", &allocator, source_type);
+        is_not_literal!("[1, , 2]", &allocator, source_type);
+        is_not_literal!("[...x]", &allocator, source_type);
+        is_not_literal!("[alert(1)]", &allocator, source_type);
+        is_not_literal!("{ x }", &allocator, source_type);
+        is_not_literal!("{ [x]: 1 }", &allocator, source_type);
+        is_not_literal!("{ ...x }", &allocator, source_type);
+        is_not_literal!("{ x() {} }", &allocator, source_type);
+        is_not_literal!("{ get x() { return 1; } }", &allocator, source_type);
+        is_not_literal!("{ \"a\": x }", &allocator, source_type);
+        is_not_literal!("{ \"a\": [1, alert(1)] }", &allocator, source_type);
+    }
+
+    #[test]
+    fn test_string_literals() {
+        let allocator = Allocator::default();
+        let source_type: SourceType = SourceType::unambiguous();
+
+        is_literal!("\"hello\"", &allocator, source_type);
+        is_literal!("'hello world'", &allocator, source_type);
+        is_literal!("\"\"", &allocator, source_type);
+        is_literal!("{\"id\": 1}", &allocator, source_type);
+        is_literal!("[true, null, \"a\", -1]", &allocator, source_type);
+        is_literal!(
+            "{\"user\": {\"name\": \"John Doe\", \"roles\": ['admin']}}",
+            &allocator,
+            source_type
+        );
+    }
+
+    #[test]
+    fn test_user_input_that_can_break_out_of_the_code() {
+        let allocator = Allocator::default();
+        let source_type: SourceType = SourceType::unambiguous();
+
+        // Strings can close a string of the code, e.g. const name = '...'
+        is_not_literal!("'; alert(1); '", &allocator, source_type);
+        is_not_literal!("' + alert(1) + '", &allocator, source_type);
+        is_not_literal!("{\"a\": \"+alert(1)+\"}", &allocator, source_type);
+        is_not_literal!("{\" + alert(1) + \": 1}", &allocator, source_type);
+        is_not_literal!("\"a\\\"b\"", &allocator, source_type);
+        is_not_literal!("\" in \"", &allocator, source_type);
+        is_not_literal!("[\"a instanceof b\"]", &allocator, source_type);
+        // e.g. const pattern = /.../
+        is_not_literal!("\"a/;alert(1);/\"", &allocator, source_type);
+        // e.g. `...${...}...`
+        is_not_literal!("\"${alert(1)}\"", &allocator, source_type);
+        // e.g. /* ... */ or // ...
+        is_not_literal!("[1, 2] /* */", &allocator, source_type);
+        is_not_literal!("[1,\n2]", &allocator, source_type);
+    }
+}
@@ -1,11 +1,14 @@
 pub mod detect_js_injection;
 pub mod detect_js_injection_test;
 
-pub mod have_comments_changed;
+pub mod find_first_changed_node;
 
-pub mod have_statements_changed;
+pub mod have_comments_changed;
 
 pub mod is_safe_js_input;
 pub mod is_safe_js_input_test;
 
+pub mod is_safe_js_literal;
+pub mod is_safe_js_literal_test;
+
 pub mod helpers;
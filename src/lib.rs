@@ -1,7 +1,12 @@
 /* Zen Vulnerability library, contains Zen vulnerability code written in Rust and exported
  * Using FFI. Currently we support the following algorithms :
  * - JS Injection
+ * - NoSQL Injection (MongoDB)
+ * - Path Traversal
  * - SQL Injection
+ * - Shell Injection
+ * - Server-side template injection (Handlebars, EJS, Nunjucks / Jinja2)
+ * - SSRF (URL analysis)
  */
 mod helpers;
 
@@ -30,3 +35,33 @@ pub mod idor;
 
 #[cfg(not(feature = "benchmarking"))]
 mod idor;
+
+#[cfg(feature = "benchmarking")]
+pub mod shell_injection;
+
+#[cfg(not(feature = "benchmarking"))]
+mod shell_injection;
+
+#[cfg(feature = "benchmarking")]
+pub mod path_traversal;
+
+#[cfg(not(feature = "benchmarking"))]
+mod path_traversal;
+
+#[cfg(feature = "benchmarking")]
+pub mod ssrf;
+
+#[cfg(not(feature = "benchmarking"))]
+mod ssrf;
+
+#[cfg(feature = "benchmarking")]
+pub mod nosql_injection;
+
+#[cfg(not(feature = "benchmarking"))]
+mod nosql_injection;
+
+#[cfg(feature = "benchmarking")]
+pub mod ssti;
+
+#[cfg(not(feature = "benchmarking"))]
+mod ssti;
@@ -0,0 +1,128 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoSqlInjectionDetectionResult {
+    pub detected: bool,
+    // Path to the user input that contains the operators, e.g. `.username`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_input_path: Option<String>,
+    // Path to the part of the filter where the operators ended up, e.g. `.username`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operators: Option<Vec<String>>,
+}
+
+/* Parses the MongoDB filter and the user input (e.g. the parsed request body) as JSON
+ * and checks them with detect_nosql_injection.
+ */
+pub fn detect_nosql_injection_str(
+    filter_json: &str,
+    userinput_json: &str,
+) -> Result<NoSqlInjectionDetectionResult, String> {
+    let filter: Value =
+        serde_json::from_str(filter_json).map_err(|e| format!("Invalid filter: {}", e))?;
+    let userinput: Value =
+        serde_json::from_str(userinput_json).map_err(|e| format!("Invalid user input: {}", e))?;
+
+    Ok(detect_nosql_injection(&filter, &userinput))
+}
+
+/* Checks if user input introduced query operators into a MongoDB filter.
+ * Walks over every object in the user input that has keys starting with `$` (e.g. `{"$ne": null}`)
+ * and looks for a part of the filter that contains all of these operators, e.g. :
+ *  user input : {"username": "admin", "password": {"$ne": null}}
+ *  filter : {"username": "admin", "password": {"$ne": null, "$exists": true}}
+ *  -> `.password` of the user input ended up as `.password` in the filter.
+ * Keys that are not operators are ignored, e.g. the user input
+ * {"username": "admin", "$where": "1"} passed as the filter (`db.find(req.body)`) is an injection.
+ */
+pub fn detect_nosql_injection(filter: &Value, userinput: &Value) -> NoSqlInjectionDetectionResult {
+    find_user_input_with_operators(filter, userinput, "").unwrap_or(NoSqlInjectionDetectionResult {
+        detected: false,
+        user_input_path: None,
+        filter_path: None,
+        operators: None,
+    })
+}
+
+fn find_user_input_with_operators(
+    filter: &Value,
+    userinput: &Value,
+    path: &str,
+) -> Option<NoSqlInjectionDetectionResult> {
+    match userinput {
+        Value::Object(object) => {
+            let operators = only_operators(object);
+            if !operators.is_empty() {
+                if let Some(filter_path) = find_filter_part(filter, &operators, "") {
+                    return Some(NoSqlInjectionDetectionResult {
+                        detected: true,
+                        user_input_path: Some(display_path(path)),
+                        filter_path: Some(display_path(&filter_path)),
+                        operators: Some(operators.keys().cloned().collect()),
+                    });
+                }
+            }
+
+            object.iter().find_map(|(key, value)| {
+                find_user_input_with_operators(filter, value, &format!("{}.{}", path, key))
+            })
+        }
+        Value::Array(array) => array.iter().enumerate().find_map(|(i, value)| {
+            find_user_input_with_operators(filter, value, &format!("{}.[{}]", path, i))
+        }),
+        _ => None,
+    }
+}
+
+/* Looks for a part of the filter that contains all operators of the user input (with the same
+ * values), the filter part can contain other keys and operators as well. Returns the path to it.
+ */
+fn find_filter_part(
+    filter_part: &Value,
+    operators: &Map<String, Value>,
+    path: &str,
+) -> Option<String> {
+    match filter_part {
+        Value::Object(object) => {
+            let contains_operators = operators
+                .iter()
+                .all(|(key, value)| object.get(key) == Some(value));
+            if contains_operators {
+                return Some(path.to_string());
+            }
+
+            object.iter().find_map(|(key, value)| {
+                find_filter_part(value, operators, &format!("{}.{}", path, key))
+            })
+        }
+        Value::Array(array) => array.iter().enumerate().find_map(|(i, value)| {
+            find_filter_part(value, operators, &format!("{}.[{}]", path, i))
+        }),
+        _ => None,
+    }
+}
+
+// The keys of the object that are operators, e.g. `{"$ne": null}` for `{"$ne": null, "a": 1}`
+fn only_operators(object: &Map<String, Value>) -> Map<String, Value> {
+    object
+        .iter()
+        .filter(|(key, _)| is_operator(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+// All MongoDB query operators start with `$`, e.g. `$ne`, `$gt`, `$regex`, `$where`, `$expr`
+fn is_operator(key: &str) -> bool {
+    key.starts_with('$')
+}
+
+fn display_path(path: &str) -> String {
+    if path.is_empty() {
+        return ".".to_string();
+    }
+
+    path.to_string()
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use crate::nosql_injection::detect_nosql_injection::{
+        detect_nosql_injection_str, NoSqlInjectionDetectionResult,
+    };
+
+    macro_rules! is_injection {
+        ($filter:expr, $input:expr) => {
+            assert!(
+                detect_nosql_injection_str($filter, $input)
+                    .unwrap()
+                    .detected,
+                "should be an injection\nfilter: {}\ninput: {}\n",
+                $filter,
+                $input
+            )
+        };
+    }
+
+    macro_rules! not_injection {
+        ($filter:expr, $input:expr) => {
+            assert!(
+                !detect_nosql_injection_str($filter, $input)
+                    .unwrap()
+                    .detected,
+                "should not be an injection\nfilter: {}\ninput: {}\n",
+                $filter,
+                $input
+            )
+        };
+    }
+
+    #[test]
+    fn test_safe_filters() {
+        not_injection!(r#"{"username": "admin"}"#, r#"{"username": "admin"}"#);
+        not_injection!(r#"{"age": {"$gt": 18}}"#, r#"{"age": 18}"#);
+        not_injection!(r#"{}"#, r#"{"username": "admin"}"#);
+        not_injection!(r#"{"username": "admin"}"#, r#"[]"#);
+        not_injection!(r#"{"username": "admin"}"#, r#""admin""#);
+    }
+
+    #[test]
+    fn test_operators_in_user_input_but_not_in_filter() {
+        not_injection!(r#"{"username": "admin"}"#, r#"{"username": {"$ne": null}}"#);
+        not_injection!(
+            r#"{"password": {"$ne": "something else"}}"#,
+            r#"{"password": {"$ne": null}}"#
+        );
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        is_injection!(
+            r#"{"username": "admin", "password": {"$ne": null}}"#,
+            r#"{"username": "admin", "password": {"$ne": null}}"#
+        );
+        is_injection!(
+            r#"{"username": "admin", "password": {"$gt": ""}}"#,
+            r#"{"username": "admin", "password": {"$gt": ""}}"#
+        );
+        is_injection!(
+            r#"{"username": {"$regex": ".*"}}"#,
+            r#"{"username": {"$regex": ".*"}}"#
+        );
+    }
+
+    #[test]
+    fn test_top_level_operators() {
+        is_injection!(
+            r#"{"$where": "sleep(1000) || true"}"#,
+            r#"{"$where": "sleep(1000) || true"}"#
+        );
+        is_injection!(
+            r#"{"$expr": {"$eq": ["$role", "admin"]}}"#,
+            r#"{"$expr": {"$eq": ["$role", "admin"]}}"#
+        );
+    }
+
+    #[test]
+    fn test_nested_filters() {
+        is_injection!(
+            r#"{"$and": [{"tenant": 1}, {"user.name": {"$ne": null}}]}"#,
+            r#"{"name": {"$ne": null}}"#
+        );
+        is_injection!(
+            r#"{"profile": {"email": {"$gt": ""}}}"#,
+            r#"{"filters": [{"email": {"$gt": ""}}]}"#
+        );
+    }
+
+    #[test]
+    fn test_user_input_passed_as_filter() {
+        // e.g. `db.find(req.body)`, only the operators of the user input are compared.
+        is_injection!(
+            r#"{"username": "admin", "$where": "1"}"#,
+            r#"{"username": "admin", "$where": "1"}"#
+        );
+        is_injection!(
+            r#"{"password": {"$ne": null}}"#,
+            r#"{"password": {"$ne": null, "comment": "test"}}"#
+        );
+        not_injection!(
+            r#"{"username": "admin", "$where": "0"}"#,
+            r#"{"username": "admin", "$where": "1"}"#
+        );
+    }
+
+    #[test]
+    fn test_filter_with_other_operators() {
+        // e.g. `{password: {...req.body.password, $exists: true}}`
+        is_injection!(
+            r#"{"password": {"$ne": null, "$exists": true}}"#,
+            r#"{"password": {"$ne": null}}"#
+        );
+        is_injection!(
+            r#"{"password": {"$ne": null, "$exists": true}}"#,
+            r#"{"password": {"$ne": null, "$exists": true}}"#
+        );
+        // All operators of the user input have to end up in the same part of the filter.
+        not_injection!(
+            r#"{"password": {"$ne": null}, "age": {"$gt": 18}}"#,
+            r#"{"password": {"$ne": null, "$gt": 18}}"#
+        );
+    }
+
+    #[test]
+    fn test_result_paths() {
+        assert_eq!(
+            detect_nosql_injection_str(
+                r#"{"username": "admin", "password": {"$ne": null}}"#,
+                r#"{"login": {"password": {"$ne": null}}}"#,
+            )
+            .unwrap(),
+            NoSqlInjectionDetectionResult {
+                detected: true,
+                user_input_path: Some(".login.password".into()),
+                filter_path: Some(".password".into()),
+                operators: Some(vec!["$ne".into()]),
+            }
+        );
+        assert_eq!(
+            detect_nosql_injection_str(
+                r#"{"$or": [{"a": 1}, {"b": {"$gt": 1, "$lt": 5}}]}"#,
+                r#"[{"$gt": 1, "$lt": 5}]"#,
+            )
+            .unwrap(),
+            NoSqlInjectionDetectionResult {
+                detected: true,
+                user_input_path: Some(".[0]".into()),
+                filter_path: Some(".$or.[1].b".into()),
+                operators: Some(vec!["$gt".into(), "$lt".into()]),
+            }
+        );
+        assert_eq!(
+            detect_nosql_injection_str(r#"{"$where": "1"}"#, r#"{"$where": "1"}"#).unwrap(),
+            NoSqlInjectionDetectionResult {
+                detected: true,
+                user_input_path: Some(".".into()),
+                filter_path: Some(".".into()),
+                operators: Some(vec!["$where".into()]),
+            }
+        );
+        assert_eq!(
+            detect_nosql_injection_str(
+                r#"{"role": {"$in": ["user", "admin"], "$exists": true}}"#,
+                r#"{"role": {"$in": ["user", "admin"], "comment": "test"}}"#,
+            )
+            .unwrap(),
+            NoSqlInjectionDetectionResult {
+                detected: true,
+                user_input_path: Some(".role".into()),
+                filter_path: Some(".role".into()),
+                operators: Some(vec!["$in".into()]),
+            }
+        );
+        assert_eq!(
+            detect_nosql_injection_str(r#"{"a": 1}"#, r#"{"a": 1}"#).unwrap(),
+            NoSqlInjectionDetectionResult {
+                detected: false,
+                user_input_path: None,
+                filter_path: None,
+                operators: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert!(detect_nosql_injection_str("{", "{}")
+            .unwrap_err()
+            .starts_with("Invalid filter"));
+        assert!(detect_nosql_injection_str("{}", "{")
+            .unwrap_err()
+            .starts_with("Invalid user input"));
+    }
+}
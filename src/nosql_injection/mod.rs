@@ -0,0 +1,2 @@
+pub mod detect_nosql_injection;
+pub mod detect_nosql_injection_test;
@@ -0,0 +1,118 @@
+use super::helpers::select_os_flavor_based_on_enum::select_os_flavor_based_on_enum;
+use super::os_flavor::OsFlavor;
+use crate::helpers::percent_decode::percent_decode;
+
+#[derive(Debug)]
+pub struct PathTraversalDetectionResult {
+    pub detected: bool,
+    pub reason: DetectionReason,
+}
+
+#[derive(Debug)]
+pub enum DetectionReason {
+    // not a path traversal
+    UserInputNotInPath,
+    UserInputTooSmall,
+    FailedToDecodeUserInput,
+    NoTraversalFound,
+    // path traversal
+    EscapesBaseDirectory,
+    AbsolutePathInjected,
+}
+
+/* Checks if the user input makes the file path escape the directory it was meant to end up in.
+ * The base directory is everything in the file path up to the last separator before the user input,
+ * e.g. for `/var/www/uploads/../../etc/passwd` with user input `../../etc/passwd` this is
+ * `/var/www/uploads/`. Makes these checks :
+ * - The user input is not an absolute path at the start of the file path (e.g. `/etc/passwd`)
+ * - Resolving the `..` segments never goes above the base directory, after percent-decoding
+ *   (e.g. `..%2f..%2fetc`) and accounting for the separators of the OS (`\` is one on Windows)
+ */
+pub fn detect_path_traversal(
+    file_path: &str,
+    user_input: &str,
+    os_flavor: i32,
+) -> PathTraversalDetectionResult {
+    let os_flavor = select_os_flavor_based_on_enum(os_flavor);
+
+    if user_input.len() <= 1 {
+        // A single character can't be used to escape a directory.
+        return PathTraversalDetectionResult {
+            detected: false,
+            reason: DetectionReason::UserInputTooSmall,
+        };
+    }
+
+    let decoded_user_input = match percent_decode(user_input) {
+        Some(decoded) => decoded,
+        None => {
+            return PathTraversalDetectionResult {
+                detected: false,
+                reason: DetectionReason::FailedToDecodeUserInput,
+            };
+        }
+    };
+
+    // Depending on the framework, the file path contains the raw or the decoded user input.
+    let user_input = if file_path.contains(user_input) {
+        user_input
+    } else if file_path.contains(&decoded_user_input) {
+        decoded_user_input.as_str()
+    } else {
+        // If the file path does not contain the user input, it's not a path traversal.
+        return PathTraversalDetectionResult {
+            detected: false,
+            reason: DetectionReason::UserInputNotInPath,
+        };
+    };
+
+    for (start, _) in file_path.match_indices(user_input) {
+        if start == 0 && os_flavor.is_absolute(&decoded_user_input) {
+            // e.g. path.resolve(userInput) with `/etc/passwd` or `C:\Windows\win.ini`
+            return PathTraversalDetectionResult {
+                detected: true,
+                reason: DetectionReason::AbsolutePathInjected,
+            };
+        }
+
+        let base_directory_len = file_path[..start]
+            .rfind(|c| os_flavor.is_separator(c))
+            .map_or(0, |separator| separator + 1);
+
+        let relative_path = &file_path[base_directory_len..];
+        let relative_path = percent_decode(relative_path).unwrap_or(relative_path.to_string());
+        if escapes_base_directory(&relative_path, os_flavor) {
+            return PathTraversalDetectionResult {
+                detected: true,
+                reason: DetectionReason::EscapesBaseDirectory,
+            };
+        }
+    }
+
+    PathTraversalDetectionResult {
+        detected: false,
+        reason: DetectionReason::NoTraversalFound,
+    }
+}
+
+/* Walks over the segments of a relative path and keeps track of how deep we are.
+ * If a `..` segment takes us above the starting directory, the path escapes it.
+ * e.g. `a/../b` stays inside, `a/../../b` escapes.
+ */
+fn escapes_base_directory(relative_path: &str, os_flavor: OsFlavor) -> bool {
+    let mut depth: usize = 0;
+    for segment in relative_path.split(|c| os_flavor.is_separator(c)) {
+        match segment {
+            ".." => {
+                if depth == 0 {
+                    return true;
+                }
+                depth -= 1;
+            }
+            "." | "" => {}
+            _ => depth += 1,
+        }
+    }
+
+    false
+}
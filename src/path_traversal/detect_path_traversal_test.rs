@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use crate::path_traversal::detect_path_traversal::{detect_path_traversal, DetectionReason};
+
+    const POSIX: i32 = 0;
+    const WINDOWS: i32 = 1;
+
+    macro_rules! is_traversal {
+        ($file_path:expr, $input:expr, $os_flavor:expr) => {
+            assert!(
+                detect_path_traversal($file_path, $input, $os_flavor).detected,
+                "should be a path traversal\nfile path: {}\ninput: {}\nos flavor: {}\n",
+                $file_path,
+                $input,
+                $os_flavor
+            )
+        };
+    }
+
+    macro_rules! not_traversal {
+        ($file_path:expr, $input:expr, $os_flavor:expr) => {
+            assert!(
+                !detect_path_traversal($file_path, $input, $os_flavor).detected,
+                "should not be a path traversal\nfile path: {}\ninput: {}\nos flavor: {}\n",
+                $file_path,
+                $input,
+                $os_flavor
+            )
+        };
+    }
+
+    #[test]
+    fn test_safe_file_names() {
+        not_traversal!("/var/www/uploads/image.png", "image.png", POSIX);
+        not_traversal!("/var/www/uploads/2024/image.png", "2024/image.png", POSIX);
+        not_traversal!("/var/www/uploads/./image.png", "./image.png", POSIX);
+        not_traversal!("/var/www/uploads/a/../image.png", "a/../image.png", POSIX);
+        not_traversal!("/var/www/uploads/image..png", "image..png", POSIX);
+        not_traversal!("/var/www/uploads/..image.png", "..image.png", POSIX);
+        not_traversal!("C:\\www\\uploads\\image.png", "image.png", WINDOWS);
+    }
+
+    #[test]
+    fn test_user_input_not_in_path() {
+        not_traversal!("/var/www/uploads/image.png", "../etc/passwd", POSIX);
+        not_traversal!("/var/www/uploads/image.png", "", POSIX);
+        not_traversal!("/var/www/uploads/image.png", ".", POSIX);
+    }
+
+    #[test]
+    fn test_dot_dot_segments() {
+        is_traversal!(
+            "/var/www/uploads/../../etc/passwd",
+            "../../etc/passwd",
+            POSIX
+        );
+        is_traversal!("/var/www/uploads/..", "..", POSIX);
+        is_traversal!("/var/www/uploads/a/../../secret", "a/../../secret", POSIX);
+        is_traversal!("/var/www/uploads/./../secret", "./../secret", POSIX);
+        is_traversal!("uploads/../secret", "../secret", POSIX);
+        is_traversal!("../secret", "../secret", POSIX);
+    }
+
+    #[test]
+    fn test_user_input_in_the_middle_of_a_segment() {
+        // The base directory is the directory containing the start of the user input.
+        is_traversal!(
+            "/var/www/uploads/img_/../../secret",
+            "_/../../secret",
+            POSIX
+        );
+        not_traversal!("/var/www/uploads/img_../../secret", "../../secret", POSIX);
+    }
+
+    #[test]
+    fn test_percent_encoded_segments() {
+        is_traversal!(
+            "/var/www/uploads/..%2f..%2fetc%2fpasswd",
+            "..%2f..%2fetc%2fpasswd",
+            POSIX
+        );
+        is_traversal!(
+            "/var/www/uploads/%2e%2e/%2e%2e/etc/passwd",
+            "%2e%2e/%2e%2e/etc/passwd",
+            POSIX
+        );
+        // The framework already decoded the user input before it ended up in the file path.
+        is_traversal!(
+            "/var/www/uploads/../../etc/passwd",
+            "..%2F..%2Fetc%2Fpasswd",
+            POSIX
+        );
+        not_traversal!("/var/www/uploads/a%20b.png", "a%20b.png", POSIX);
+    }
+
+    #[test]
+    fn test_backslash_separators() {
+        is_traversal!(
+            "C:\\www\\uploads\\..\\..\\win.ini",
+            "..\\..\\win.ini",
+            WINDOWS
+        );
+        is_traversal!(
+            "C:\\www\\uploads\\../..\\win.ini",
+            "../..\\win.ini",
+            WINDOWS
+        );
+        is_traversal!(
+            "C:\\www\\uploads\\..%5c..%5cwin.ini",
+            "..%5c..%5cwin.ini",
+            WINDOWS
+        );
+        // A backslash is a regular character in a POSIX file name.
+        not_traversal!("/var/www/uploads/..\\..\\passwd", "..\\..\\passwd", POSIX);
+    }
+
+    #[test]
+    fn test_absolute_paths() {
+        is_traversal!("/etc/passwd", "/etc/passwd", POSIX);
+        is_traversal!("C:\\Windows\\win.ini", "C:\\Windows\\win.ini", WINDOWS);
+        is_traversal!("C:/Windows/win.ini", "C:/Windows/win.ini", WINDOWS);
+        is_traversal!(
+            "\\\\server\\share\\file",
+            "\\\\server\\share\\file",
+            WINDOWS
+        );
+        is_traversal!("%2fetc%2fpasswd", "%2fetc%2fpasswd", POSIX);
+        not_traversal!("C:\\Windows\\win.ini", "C:\\Windows\\win.ini", POSIX);
+        // Joining an absolute path with a base directory keeps it inside of the base directory.
+        not_traversal!("/var/www/uploads//etc/passwd", "/etc/passwd", POSIX);
+    }
+
+    #[test]
+    fn test_reasons() {
+        assert!(matches!(
+            detect_path_traversal("/var/www/uploads/a.png", "a", POSIX).reason,
+            DetectionReason::UserInputTooSmall
+        ));
+        assert!(matches!(
+            detect_path_traversal("/var/www/uploads/a.png", "%C3", POSIX).reason,
+            DetectionReason::FailedToDecodeUserInput
+        ));
+        assert!(matches!(
+            detect_path_traversal("/var/www/uploads/a.png", "b.png", POSIX).reason,
+            DetectionReason::UserInputNotInPath
+        ));
+        assert!(matches!(
+            detect_path_traversal("/var/www/uploads/a.png", "a.png", POSIX).reason,
+            DetectionReason::NoTraversalFound
+        ));
+        assert!(matches!(
+            detect_path_traversal("/var/www/uploads/../a.png", "../a.png", POSIX).reason,
+            DetectionReason::EscapesBaseDirectory
+        ));
+        assert!(matches!(
+            detect_path_traversal("/etc/passwd", "/etc/passwd", POSIX).reason,
+            DetectionReason::AbsolutePathInjected
+        ));
+    }
+}
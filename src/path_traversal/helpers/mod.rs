@@ -0,0 +1 @@
+pub mod select_os_flavor_based_on_enum;
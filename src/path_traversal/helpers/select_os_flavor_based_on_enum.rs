@@ -0,0 +1,28 @@
+use crate::path_traversal::os_flavor::OsFlavor;
+
+/*
+0 -> POSIX (Linux, macOS, ...)
+1 -> Windows
+Default -> POSIX
+*/
+pub fn select_os_flavor_based_on_enum(enumerator: i32) -> OsFlavor {
+    // 0 is POSIX.
+    match enumerator {
+        0 => OsFlavor::Posix,
+        1 => OsFlavor::Windows,
+        _ => OsFlavor::Posix,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path_traversal::helpers::select_os_flavor_based_on_enum::select_os_flavor_based_on_enum;
+    use crate::path_traversal::os_flavor::OsFlavor;
+
+    #[test]
+    fn test_select_os_flavor_based_on_enum() {
+        assert_eq!(select_os_flavor_based_on_enum(0), OsFlavor::Posix);
+        assert_eq!(select_os_flavor_based_on_enum(1), OsFlavor::Windows);
+        assert_eq!(select_os_flavor_based_on_enum(2), OsFlavor::Posix);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod detect_path_traversal;
+pub mod detect_path_traversal_test;
+
+pub mod helpers;
+
+pub mod os_flavor;
@@ -0,0 +1,30 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OsFlavor {
+    Posix,
+    Windows,
+}
+
+impl OsFlavor {
+    // Windows accepts both `\` and `/` as separators, POSIX only `/`.
+    pub fn is_separator(&self, c: char) -> bool {
+        match self {
+            OsFlavor::Posix => c == '/',
+            OsFlavor::Windows => c == '/' || c == '\\',
+        }
+    }
+
+    /* Checks if the path is absolute for this OS :
+     * - POSIX : `/etc/passwd`
+     * - Windows : `C:\Windows`, `C:/Windows`, `\\server\share` or `\Windows` (root of current drive)
+     */
+    pub fn is_absolute(&self, path: &str) -> bool {
+        let mut chars = path.chars();
+        match chars.next() {
+            Some(c) if self.is_separator(c) => true,
+            Some(drive) if *self == OsFlavor::Windows && drive.is_ascii_alphabetic() => {
+                chars.next() == Some(':') && chars.next().is_some_and(|c| self.is_separator(c))
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use super::tokenize_command::tokenize_command;
+use crate::helpers::have_tokens_changed::have_tokens_changed;
+
+pub fn detect_shell_injection_str(command: &str, userinput: &str) -> bool {
+    if userinput.len() <= 1 {
+        // We assume that a single character cannot be an injection.
+        // It could only break the command, not execute an arbitrary one.
+        return false;
+    }
+
+    if userinput.len() > command.len() {
+        // If the user input is longer than the command, it's not an injection.
+        return false;
+    }
+
+    if !command.contains(userinput) {
+        // If the command does not contain the user input, it's not an injection.
+        return false;
+    }
+
+    // Tokenize command :
+    let tokens = tokenize_command(command);
+    if tokens.is_empty() {
+        // Tokens are empty, probably a parsing issue with original command, return false.
+        return false;
+    }
+
+    // Replace user input with string of equal length and tokenize again :
+    let safe_replace_str = "a".repeat(userinput.len());
+    let command_without_input = command.replace(userinput, &safe_replace_str);
+    let tokens_without_input = tokenize_command(&command_without_input);
+
+    // If the structure of the command changed, the user input is interpreted as shell syntax.
+    have_tokens_changed(&tokens, &tokens_without_input)
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::shell_injection::detect_shell_injection::detect_shell_injection_str;
+
+    macro_rules! is_injection {
+        ($command:expr, $input:expr) => {
+            assert!(
+                detect_shell_injection_str($command, $input),
+                "should be an injection\ncommand: {}\ninput: {}\n",
+                $command,
+                $input
+            )
+        };
+    }
+
+    macro_rules! not_injection {
+        ($command:expr, $input:expr) => {
+            assert!(
+                !detect_shell_injection_str($command, $input),
+                "should not be an injection\ncommand: {}\ninput: {}\n",
+                $command,
+                $input
+            )
+        };
+    }
+
+    #[test]
+    fn test_single_characters_are_ignored() {
+        not_injection!("ls `", "`");
+        not_injection!("ls *", "*");
+        not_injection!("ls a", "a");
+    }
+
+    #[test]
+    fn test_user_input_not_in_command() {
+        not_injection!("ls", "");
+        not_injection!("ls", "$(echo)");
+        not_injection!("ls -la", "ls -la; whoami");
+    }
+
+    #[test]
+    fn test_safe_arguments() {
+        not_injection!("ls -la /tmp/uploads", "/tmp/uploads");
+        not_injection!("ls -la", "-la");
+        not_injection!("echo 'hello world'", "hello world");
+        not_injection!("echo \"hello world\"", "hello world");
+        not_injection!("convert image.png image.jpg", "image.png");
+        not_injection!("git log --author=john", "john");
+        not_injection!("echo 'john; doe'", "john; doe");
+        not_injection!("echo 'name && id'", "name && id");
+        not_injection!("echo '$(whoami)'", "$(whoami)");
+        not_injection!("echo '`id`'", "`id`");
+    }
+
+    #[test]
+    fn test_command_separators() {
+        is_injection!("ls; whoami", "; whoami");
+        is_injection!("ls /tmp; rm -rf /", "/tmp; rm -rf /");
+        is_injection!("ls && whoami", "&& whoami");
+        is_injection!("ls || whoami", "|| whoami");
+        is_injection!("ls & whoami", "& whoami");
+        is_injection!("cat file | sh", "file | sh");
+        is_injection!("ls\nwhoami", "\nwhoami");
+    }
+
+    #[test]
+    fn test_substitutions() {
+        is_injection!("echo $(whoami)", "$(whoami)");
+        is_injection!("echo `whoami`", "`whoami`");
+        is_injection!("echo \"hello $(whoami)\"", "$(whoami)");
+        is_injection!("echo \"hello `whoami`\"", "`whoami`");
+        is_injection!("cat <(whoami)", "<(whoami)");
+    }
+
+    #[test]
+    fn test_expansions() {
+        is_injection!("echo $HOME", "$HOME");
+        is_injection!("echo ${HOME}", "${HOME}");
+        is_injection!("echo \"$PATH\"", "$PATH");
+        is_injection!("ls $1", "$1");
+    }
+
+    #[test]
+    fn test_redirections() {
+        is_injection!("echo hello > /etc/passwd", "hello > /etc/passwd");
+        is_injection!("sort < /etc/shadow", "< /etc/shadow");
+        is_injection!("echo hello >> ~/.bashrc", "hello >> ~/.bashrc");
+    }
+
+    #[test]
+    fn test_quote_breakouts() {
+        is_injection!("echo 'hello'; whoami; ''", "hello'; whoami; '");
+        is_injection!("echo \"hello\"; whoami; \"\"", "hello\"; whoami; \"");
+        is_injection!("git clone 'a' 'b'", "a' 'b");
+    }
+
+    #[test]
+    fn test_comments() {
+        is_injection!("ls # -la", "# -la");
+        is_injection!("ls file # -la", "file # -la");
+        not_injection!("ls -la # list files", "-la");
+    }
+
+    #[test]
+    fn test_invalid_command_without_user_input() {
+        // The command is invalid without user input, the author expected it to contain shell syntax.
+        not_injection!("echo 'hello", "hello");
+        not_injection!("echo $(whoami", "whoami");
+    }
+
+    #[test]
+    fn test_multiple_occurrences() {
+        not_injection!("cp file.txt file.txt.bak", "file.txt");
+        is_injection!("echo a; echo b; id", "; echo b; id");
+    }
+}
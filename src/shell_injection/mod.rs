@@ -0,0 +1,5 @@
+pub mod detect_shell_injection;
+pub mod detect_shell_injection_test;
+
+pub mod tokenize_command;
+pub mod tokenize_command_test;
@@ -0,0 +1,313 @@
+use crate::helpers::have_tokens_changed::StructuralToken;
+
+/*
+ * Tokenizer for POSIX sh/bash command lines. It does not try to execute or fully parse
+ * the command, it only splits it into the tokens that define its structure :
+ * - Words (arguments, including their quotes, e.g. `'hello world'` or `"$HOME"/file`)
+ * - Control and redirection operators (`;`, `&&`, `|`, `>>`, `(`, newlines, ...)
+ * - Substitutions (`$(`, `` ` ``, `<(`, `>(`), the commands inside are tokenized as well
+ * - Parameter expansions (`$HOME`, `${var}`, `$1`, `$?`, ...)
+ * - Comments (`# ...`)
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShellToken {
+    Word(String),
+    Operator(String),
+    Substitution(String),
+    Expansion(String),
+    Comment(String),
+}
+
+impl StructuralToken for ShellToken {
+    fn is_altered(&self, other: &Self) -> bool {
+        match (self, other) {
+            // e.g. `;` became `&&`
+            (ShellToken::Operator(operator1), ShellToken::Operator(operator2)) => {
+                operator1 != operator2
+            }
+            (ShellToken::Substitution(substitution1), ShellToken::Substitution(substitution2)) => {
+                substitution1 != substitution2
+            }
+            (ShellToken::Comment(comment1), ShellToken::Comment(comment2)) => {
+                comment1.len() != comment2.len()
+            }
+            // Words and expansions are allowed to change, that's where the user input ends up.
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct UnterminatedError;
+
+// Ordered so that the longest operators are matched first.
+const OPERATORS: [&str; 20] = [
+    "<<<", "<<-", "&>>", "&&", "||", ";;", ";&", "|&", ">>", "<<", "<&", ">&", "<>", ">|", "&>",
+    ";", "&", "|", "<", ">",
+];
+
+const SPECIAL_PARAMETERS: [char; 7] = ['@', '*', '#', '?', '$', '!', '-'];
+
+pub fn tokenize_command(command: &str) -> Vec<ShellToken> {
+    let mut tokenizer = ShellTokenizer {
+        chars: command.chars().collect(),
+        pos: 0,
+        word: String::new(),
+        tokens: Vec::new(),
+    };
+
+    // An unterminated quote or substitution means we can't reason about the structure.
+    match tokenizer.tokenize(None) {
+        Ok(()) => tokenizer.tokens,
+        Err(UnterminatedError) => Vec::new(),
+    }
+}
+
+struct ShellTokenizer {
+    chars: Vec<char>,
+    pos: usize,
+    word: String,
+    tokens: Vec<ShellToken>,
+}
+
+impl ShellTokenizer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn flush_word(&mut self) {
+        if !self.word.is_empty() {
+            self.tokens
+                .push(ShellToken::Word(std::mem::take(&mut self.word)));
+        }
+    }
+
+    /* Tokenizes until the end of the input, or until `closing` is found when tokenizing
+     * the command inside of a substitution (`)` for `$(...)`, `` ` `` for backticks).
+     */
+    fn tokenize(&mut self, closing: Option<char>) -> Result<(), UnterminatedError> {
+        // Keeps track of subshells inside of a `$(...)` so we know which `)` closes it.
+        let mut paren_depth = 0;
+
+        while let Some(c) = self.peek() {
+            if closing == Some(c) && (c == '`' || paren_depth == 0) {
+                self.pos += 1;
+                self.flush_word();
+                if c == '`' {
+                    self.tokens.push(ShellToken::Substitution(c.to_string()));
+                } else {
+                    self.tokens.push(ShellToken::Operator(c.to_string()));
+                }
+                return Ok(());
+            }
+
+            match c {
+                ' ' | '\t' => {
+                    self.pos += 1;
+                    self.flush_word();
+                }
+                '\n' => {
+                    self.pos += 1;
+                    self.flush_word();
+                    self.tokens.push(ShellToken::Operator(c.to_string()));
+                }
+                '#' if self.word.is_empty() => self.read_comment(),
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        // Line continuation, the shell removes both characters.
+                        Some('\n') => self.pos += 1,
+                        Some(escaped) => {
+                            self.word.push('\\');
+                            self.word.push(escaped);
+                            self.pos += 1;
+                        }
+                        None => self.word.push('\\'),
+                    }
+                }
+                '\'' => self.read_single_quoted()?,
+                '"' => self.read_double_quoted()?,
+                '$' => self.read_dollar()?,
+                '`' => {
+                    self.pos += 1;
+                    self.flush_word();
+                    self.tokens.push(ShellToken::Substitution(c.to_string()));
+                    self.tokenize(Some('`'))?;
+                }
+                '<' | '>' if self.peek_at(1) == Some('(') => {
+                    // Process substitution, e.g. `diff <(ls a) <(ls b)`
+                    self.pos += 2;
+                    self.flush_word();
+                    self.tokens
+                        .push(ShellToken::Substitution(format!("{}(", c)));
+                    self.tokenize(Some(')'))?;
+                }
+                '(' | ')' => {
+                    self.pos += 1;
+                    self.flush_word();
+                    if c == '(' {
+                        paren_depth += 1;
+                    } else if paren_depth > 0 {
+                        paren_depth -= 1;
+                    }
+                    self.tokens.push(ShellToken::Operator(c.to_string()));
+                }
+                ';' | '&' | '|' | '<' | '>' => {
+                    self.flush_word();
+                    self.read_operator();
+                }
+                _ => {
+                    self.pos += 1;
+                    self.word.push(c);
+                }
+            }
+        }
+
+        if closing.is_some() {
+            return Err(UnterminatedError);
+        }
+
+        self.flush_word();
+        Ok(())
+    }
+
+    fn read_comment(&mut self) {
+        let mut comment = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            comment.push(c);
+            self.pos += 1;
+        }
+        self.tokens.push(ShellToken::Comment(comment));
+    }
+
+    fn read_operator(&mut self) {
+        for operator in OPERATORS {
+            let matches = operator
+                .chars()
+                .enumerate()
+                .all(|(i, c)| self.peek_at(i) == Some(c));
+
+            if matches {
+                self.pos += operator.chars().count();
+                self.tokens.push(ShellToken::Operator(operator.to_string()));
+                return;
+            }
+        }
+    }
+
+    // Single quotes can't contain any expansions or escapes, e.g. 'hello $USER'
+    fn read_single_quoted(&mut self) -> Result<(), UnterminatedError> {
+        self.word.push('\'');
+        self.pos += 1;
+        while let Some(c) = self.peek() {
+            self.word.push(c);
+            self.pos += 1;
+            if c == '\'' {
+                return Ok(());
+            }
+        }
+
+        Err(UnterminatedError)
+    }
+
+    // Double quotes still allow expansions and substitutions, e.g. "hello $USER $(whoami)"
+    fn read_double_quoted(&mut self) -> Result<(), UnterminatedError> {
+        self.word.push('"');
+        self.pos += 1;
+        while let Some(c) = self.peek() {
+            match c {
+                '"' => {
+                    self.word.push(c);
+                    self.pos += 1;
+                    return Ok(());
+                }
+                '\\' => {
+                    self.word.push(c);
+                    self.pos += 1;
+                    if let Some(escaped) = self.peek() {
+                        self.word.push(escaped);
+                        self.pos += 1;
+                    }
+                }
+                '$' => self.read_dollar()?,
+                '`' => {
+                    self.pos += 1;
+                    self.flush_word();
+                    self.tokens.push(ShellToken::Substitution(c.to_string()));
+                    self.tokenize(Some('`'))?;
+                }
+                _ => {
+                    self.word.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        Err(UnterminatedError)
+    }
+
+    fn read_dollar(&mut self) -> Result<(), UnterminatedError> {
+        match self.peek_at(1) {
+            Some('(') => {
+                // Command substitution `$(...)` or arithmetic expansion `$((...))`
+                self.pos += 2;
+                self.flush_word();
+                self.tokens.push(ShellToken::Substitution("$(".to_string()));
+                self.tokenize(Some(')'))
+            }
+            Some('{') => {
+                self.flush_word();
+                let mut expansion = String::from("${");
+                self.pos += 2;
+                let mut depth = 1;
+                while let Some(c) = self.peek() {
+                    expansion.push(c);
+                    self.pos += 1;
+                    if c == '{' {
+                        depth += 1;
+                    } else if c == '}' {
+                        depth -= 1;
+                        if depth == 0 {
+                            self.tokens.push(ShellToken::Expansion(expansion));
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(UnterminatedError)
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                self.flush_word();
+                let mut expansion = String::from("$");
+                self.pos += 1;
+                while let Some(c) = self.peek() {
+                    if !c.is_ascii_alphanumeric() && c != '_' {
+                        break;
+                    }
+                    expansion.push(c);
+                    self.pos += 1;
+                }
+                self.tokens.push(ShellToken::Expansion(expansion));
+                Ok(())
+            }
+            Some(c) if c.is_ascii_digit() || SPECIAL_PARAMETERS.contains(&c) => {
+                self.flush_word();
+                self.pos += 2;
+                self.tokens.push(ShellToken::Expansion(format!("${}", c)));
+                Ok(())
+            }
+            _ => {
+                // A lone `$` is just a literal character.
+                self.word.push('$');
+                self.pos += 1;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use crate::shell_injection::tokenize_command::tokenize_command;
+    use crate::shell_injection::tokenize_command::ShellToken::{
+        Comment, Expansion, Operator, Substitution, Word,
+    };
+
+    macro_rules! word {
+        ($value:expr) => {
+            Word($value.to_string())
+        };
+    }
+
+    macro_rules! op {
+        ($value:expr) => {
+            Operator($value.to_string())
+        };
+    }
+
+    #[test]
+    fn test_tokenize_simple_command() {
+        assert_eq!(
+            tokenize_command("ls -la /tmp"),
+            vec![word!("ls"), word!("-la"), word!("/tmp")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_operators() {
+        assert_eq!(
+            tokenize_command("ls; whoami && id || true | cat > out 2>&1 &"),
+            vec![
+                word!("ls"),
+                op!(";"),
+                word!("whoami"),
+                op!("&&"),
+                word!("id"),
+                op!("||"),
+                word!("true"),
+                op!("|"),
+                word!("cat"),
+                op!(">"),
+                word!("out"),
+                word!("2"),
+                op!(">&"),
+                word!("1"),
+                op!("&"),
+            ]
+        );
+        assert_eq!(
+            tokenize_command("cat <<< hello >> log\nid"),
+            vec![
+                word!("cat"),
+                op!("<<<"),
+                word!("hello"),
+                op!(">>"),
+                word!("log"),
+                op!("\n"),
+                word!("id"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_quotes() {
+        assert_eq!(
+            tokenize_command("echo 'hello; world' \"it's\""),
+            vec![word!("echo"), word!("'hello; world'"), word!("\"it's\"")]
+        );
+        assert_eq!(
+            tokenize_command("echo 'hello $USER'"),
+            vec![word!("echo"), word!("'hello $USER'")]
+        );
+        assert_eq!(
+            tokenize_command("echo \"a\\\"b\" c\\ d"),
+            vec![word!("echo"), word!("\"a\\\"b\""), word!("c\\ d")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_expansions() {
+        assert_eq!(
+            tokenize_command("echo $HOME ${PATH} $1 $? \"$USER\"/file"),
+            vec![
+                word!("echo"),
+                Expansion("$HOME".to_string()),
+                Expansion("${PATH}".to_string()),
+                Expansion("$1".to_string()),
+                Expansion("$?".to_string()),
+                word!("\""),
+                Expansion("$USER".to_string()),
+                word!("\"/file"),
+            ]
+        );
+        assert_eq!(
+            tokenize_command("echo price: 5$"),
+            vec![word!("echo"), word!("price:"), word!("5$")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_substitutions() {
+        assert_eq!(
+            tokenize_command("echo $(whoami) `id`"),
+            vec![
+                word!("echo"),
+                Substitution("$(".to_string()),
+                word!("whoami"),
+                op!(")"),
+                Substitution("`".to_string()),
+                word!("id"),
+                Substitution("`".to_string()),
+            ]
+        );
+        assert_eq!(
+            tokenize_command("echo \"user: $(id -un)\""),
+            vec![
+                word!("echo"),
+                word!("\"user: "),
+                Substitution("$(".to_string()),
+                word!("id"),
+                word!("-un"),
+                op!(")"),
+                word!("\""),
+            ]
+        );
+        assert_eq!(
+            tokenize_command("diff <(ls a) b"),
+            vec![
+                word!("diff"),
+                Substitution("<(".to_string()),
+                word!("ls"),
+                word!("a"),
+                op!(")"),
+                word!("b"),
+            ]
+        );
+        assert_eq!(
+            tokenize_command("echo $((1 + (2)))"),
+            vec![
+                word!("echo"),
+                Substitution("$(".to_string()),
+                op!("("),
+                word!("1"),
+                word!("+"),
+                op!("("),
+                word!("2"),
+                op!(")"),
+                op!(")"),
+                op!(")"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_comments() {
+        assert_eq!(
+            tokenize_command("ls # list files\nid"),
+            vec![
+                word!("ls"),
+                Comment("# list files".to_string()),
+                op!("\n"),
+                word!("id"),
+            ]
+        );
+        assert_eq!(
+            tokenize_command("echo a#b"),
+            vec![word!("echo"), word!("a#b")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_line_continuation() {
+        assert_eq!(
+            tokenize_command("ls \\\n-la"),
+            vec![word!("ls"), word!("-la")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_error_handling() {
+        assert_eq!(tokenize_command("echo 'unterminated"), vec![]);
+        assert_eq!(tokenize_command("echo \"unterminated"), vec![]);
+        assert_eq!(tokenize_command("echo $(whoami"), vec![]);
+        assert_eq!(tokenize_command("echo `whoami"), vec![]);
+        assert_eq!(tokenize_command("echo ${HOME"), vec![]);
+        assert_eq!(tokenize_command(""), vec![]);
+    }
+}
@@ -0,0 +1,136 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::{PoisonError, RwLock};
+
+/* The exemptions that are consulted alongside the built-in exemptions of is_common_sql_string.rs,
+ * for app-specific user input that is not an injection (e.g. a filter parameter like
+ * `price > 10`). They are set at runtime from JSON (see set_custom_exemptions_json),
+ * setting them again replaces the previous ones :
+ * { "strings": ["price > 10"], "regexes": ["^price (<|>) [0-9]+$"] }
+ * The user input is lowercased before it's compared, so the strings are lowercased when they
+ * are set and the regexes should only match lowercase characters. A regex has to match the
+ * whole user input, e.g. `price > 10; drop table users` doesn't match `price (<|>) [0-9]+`.
+ * The exemptions are shared by the whole process, so they apply to every detection.
+ */
+static CUSTOM_EXEMPTIONS: RwLock<CustomExemptions> = RwLock::new(CustomExemptions::new());
+
+#[derive(Default)]
+pub struct CustomExemptions {
+    strings: Vec<String>,
+    // The pattern as it was set (reported as the rule) and the regex that matches the whole input.
+    regexes: Vec<(String, Regex)>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomExemptionsConfig {
+    #[serde(default)]
+    strings: Vec<String>,
+    #[serde(default)]
+    regexes: Vec<String>,
+}
+
+// The exemption (rule) that matched the user input, reported with the reason CommonSQLString.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SqlExemption {
+    pub kind: SqlExemptionKind,
+    // The string or regex (pattern) that matched the user input, or the sort list itself.
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum SqlExemptionKind {
+    BuiltIn,
+    CustomString,
+    CustomRegex,
+    // The user input is a list of columns in the ORDER BY clause (see is_order_by_list.rs).
+    OrderByList,
+}
+
+impl SqlExemption {
+    pub fn built_in(rule: &str) -> Self {
+        SqlExemption {
+            kind: SqlExemptionKind::BuiltIn,
+            rule: rule.to_string(),
+        }
+    }
+
+    pub fn order_by_list(list: &str) -> Self {
+        SqlExemption {
+            kind: SqlExemptionKind::OrderByList,
+            rule: list.to_string(),
+        }
+    }
+}
+
+impl CustomExemptions {
+    pub const fn new() -> Self {
+        CustomExemptions {
+            strings: Vec::new(),
+            regexes: Vec::new(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: CustomExemptionsConfig =
+            serde_json::from_str(json).map_err(|e| format!("Invalid exemptions: {}", e))?;
+
+        let regexes = config
+            .regexes
+            .into_iter()
+            .map(|pattern| {
+                // The pattern is checked on its own first, so it can't escape the anchored group.
+                Regex::new(&pattern)
+                    .and_then(|_| Regex::new(&format!("^(?:{})$", pattern)))
+                    .map(|regex| (pattern, regex))
+                    .map_err(|e| format!("Invalid regex: {}", e))
+            })
+            .collect::<Result<Vec<(String, Regex)>, String>>()?;
+        let strings = config
+            .strings
+            .iter()
+            .map(|string| string.to_lowercase())
+            .collect();
+
+        Ok(CustomExemptions { strings, regexes })
+    }
+
+    // Returns the exemption that matches the (lowercased) user input.
+    pub fn find(&self, user_input: &str) -> Option<SqlExemption> {
+        if let Some(string) = self.strings.iter().find(|s| *s == user_input) {
+            return Some(SqlExemption {
+                kind: SqlExemptionKind::CustomString,
+                rule: string.clone(),
+            });
+        }
+
+        self.regexes
+            .iter()
+            .find(|(_, regex)| regex.is_match(user_input))
+            .map(|(pattern, _)| SqlExemption {
+                kind: SqlExemptionKind::CustomRegex,
+                rule: pattern.clone(),
+            })
+    }
+}
+
+/* Parses the custom exemptions from JSON and replaces the current ones of the process. If the JSON
+ * or one of the regexes is invalid, the current exemptions are kept.
+ */
+pub fn set_custom_exemptions_json(json: &str) -> Result<(), String> {
+    let exemptions = CustomExemptions::from_json(json)?;
+
+    *CUSTOM_EXEMPTIONS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = exemptions;
+
+    Ok(())
+}
+
+// Returns the custom exemption of the process that matches the (lowercased) user input.
+pub fn find_custom_exemption(user_input: &str) -> Option<SqlExemption> {
+    CUSTOM_EXEMPTIONS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .find(user_input)
+}
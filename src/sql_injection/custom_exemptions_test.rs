@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::custom_exemptions::{
+        find_custom_exemption, set_custom_exemptions_json, CustomExemptions, SqlExemption,
+        SqlExemptionKind,
+    };
+    use crate::sql_injection::detect_sql_injection::{detect_sql_injection_str, DetectionReason};
+    use crate::sql_injection::is_common_sql_string::find_exemption;
+
+    fn custom_string(rule: &str) -> Option<SqlExemption> {
+        Some(SqlExemption {
+            kind: SqlExemptionKind::CustomString,
+            rule: rule.to_string(),
+        })
+    }
+
+    fn custom_regex(rule: &str) -> Option<SqlExemption> {
+        Some(SqlExemption {
+            kind: SqlExemptionKind::CustomRegex,
+            rule: rule.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_find_custom_string() {
+        let exemptions = CustomExemptions::from_json(r#"{"strings": ["Price > 10"]}"#).unwrap();
+        assert_eq!(exemptions.find("price > 10"), custom_string("price > 10"));
+        assert_eq!(exemptions.find("price > 100"), None);
+        assert_eq!(exemptions.find("price > 10; drop table users"), None);
+        assert_eq!(exemptions.find(""), None);
+    }
+
+    #[test]
+    fn test_find_custom_regex() {
+        let exemptions =
+            CustomExemptions::from_json(r#"{"regexes": ["^sort=[a-z_]+:(asc|desc)$"]}"#).unwrap();
+        assert_eq!(
+            exemptions.find("sort=name:desc"),
+            custom_regex("^sort=[a-z_]+:(asc|desc)$")
+        );
+        assert_eq!(exemptions.find("sort=name:desc; drop"), None);
+    }
+
+    #[test]
+    fn test_custom_regex_matches_whole_user_input() {
+        let exemptions = CustomExemptions::from_json(
+            r#"{"regexes": ["price (<|>) [0-9]+", "created_at desc", "a|ab"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            exemptions.find("price > 10"),
+            custom_regex("price (<|>) [0-9]+")
+        );
+        assert_eq!(exemptions.find("price > 10; drop table users"), None);
+        assert_eq!(exemptions.find("1 or price > 10"), None);
+        assert_eq!(
+            exemptions.find("created_at desc"),
+            custom_regex("created_at desc")
+        );
+        assert_eq!(exemptions.find("created_at desc; drop table users"), None);
+        // Every alternative has to match the whole user input.
+        assert_eq!(exemptions.find("ab"), custom_regex("a|ab"));
+        assert_eq!(exemptions.find("abc"), None);
+    }
+
+    #[test]
+    fn test_custom_regex_cant_escape_anchors() {
+        assert!(CustomExemptions::from_json(r#"{"regexes": ["a)|(b"]}"#)
+            .err()
+            .unwrap()
+            .starts_with("Invalid regex: "));
+        assert!(CustomExemptions::from_json(r#"{"regexes": ["a)|.*|(b"]}"#).is_err());
+    }
+
+    #[test]
+    fn test_invalid_custom_exemptions() {
+        assert!(CustomExemptions::from_json(r#"{"regexes": ["("]}"#)
+            .err()
+            .unwrap()
+            .starts_with("Invalid regex: "));
+        assert!(CustomExemptions::from_json(r#"{"string": ["a"]}"#)
+            .err()
+            .unwrap()
+            .starts_with("Invalid exemptions: "));
+        assert!(CustomExemptions::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_empty_custom_exemptions() {
+        let exemptions = CustomExemptions::from_json("{}").unwrap();
+        assert_eq!(exemptions.find("price > 10"), None);
+        assert_eq!(CustomExemptions::new().find("price > 10"), None);
+    }
+
+    // The exemptions of the process are global, so they are only set in this test (tests run in parallel).
+    #[test]
+    fn test_set_custom_exemptions() {
+        let query = "SELECT * FROM products WHERE price > 10";
+        let result = detect_sql_injection_str(query, "price > 10", 9);
+        assert!(result.detected);
+        assert_eq!(result.exemption, None);
+
+        assert_eq!(
+            set_custom_exemptions_json(
+                r#"{"strings": ["Price > 10"], "regexes": ["^sort=[a-z_]+:(asc|desc)$"]}"#
+            ),
+            Ok(())
+        );
+
+        let result = detect_sql_injection_str(query, "price > 10", 9);
+        assert!(!result.detected);
+        assert!(matches!(result.reason, DetectionReason::CommonSQLString));
+        assert_eq!(result.exemption, custom_string("price > 10"));
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "detected": false,
+                "reason": "CommonSQLString",
+                "span": { "start": 29, "end": 39 },
+                "exemption": { "kind": "CustomString", "rule": "price > 10" }
+            })
+        );
+        assert!(
+            detect_sql_injection_str(
+                "SELECT * FROM products WHERE price > 10; drop table users",
+                "price > 10; drop table users",
+                9
+            )
+            .detected
+        );
+
+        assert_eq!(
+            find_custom_exemption("sort=name:desc"),
+            custom_regex("^sort=[a-z_]+:(asc|desc)$")
+        );
+        assert!(find_exemption("sort=name:asc").is_some());
+
+        // The built-in exemptions are checked first.
+        assert_eq!(
+            find_exemption("select *"),
+            Some(SqlExemption::built_in("select *"))
+        );
+
+        // Invalid exemptions don't replace the current ones.
+        assert!(set_custom_exemptions_json(r#"{"regexes": ["("]}"#).is_err());
+        assert!(find_exemption("price > 10").is_some());
+
+        assert_eq!(set_custom_exemptions_json("{}"), Ok(()));
+        assert_eq!(find_custom_exemption("price > 10"), None);
+        assert_eq!(find_exemption("sort=name:asc"), None);
+        assert!(detect_sql_injection_str(query, "price > 10", 9).detected);
+    }
+}
@@ -1,17 +1,36 @@
+use super::custom_exemptions::{SqlExemption, SqlExemptionKind};
 use super::have_comments_changed::have_comments_changed;
-use super::is_common_sql_string::is_common_sql_string;
+use super::have_token_categories_changed::have_token_categories_changed;
+use super::helpers::find_case_insensitive::{find_case_insensitive, CaseInsensitiveStr};
+use super::helpers::split_statements::split_statements;
+use super::is_common_sql_string::find_exemption;
+use super::is_order_by_list::is_order_by_list_in_query;
+use super::sql_dialect::SqlDialect;
 use super::tokenize_query::tokenize_query;
+use super::user_input_decoding::UserInputDecoding;
 use crate::diff_in_vec_len;
+use serde::Serialize;
+use sqlparser::tokenizer::Token;
+use std::ops::Range;
 
 const SPACE_CHAR: char = ' ';
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct SqlInjectionDetectionResult {
     pub detected: bool,
     pub reason: DetectionReason,
+    // Byte offsets of the (first occurrence of the) user input in the query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Range<usize>>,
+    // The decoding of the user input that was found in the query (see detect_sql_injection_with_decodings).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoding: Option<UserInputDecoding>,
+    // The exemption that matched the user input (when the reason is CommonSQLString).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exemption: Option<SqlExemption>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum DetectionReason {
     // not an injection
     UserInputNotInQuery,
@@ -19,9 +38,18 @@ pub enum DetectionReason {
     FailedToTokenizeQuery,
     UserInputTooSmall,
     NoChangesFound,
+    // The statement around the user input is larger than the maximum length (not checked).
+    QueryTooLarge,
     // injection
     TokensHaveDelta,
     CommentStructureAltered,
+    TokenKindsChanged,
+}
+
+// How the occurrences of the user input in the query are replaced before tokenizing it again.
+enum ReplaceMode {
+    AllOccurrences,
+    EachOccurrence,
 }
 
 pub fn detect_sql_injection_str(
@@ -29,23 +57,380 @@ pub fn detect_sql_injection_str(
     userinput_raw: &str,
     dialect: i32,
 ) -> SqlInjectionDetectionResult {
-    let query: String = query_raw.to_lowercase();
+    let query = CaseInsensitiveStr::new(query_raw);
+
+    detect_sql_injection_in_query(
+        &query,
+        userinput_raw,
+        dialect,
+        &mut None,
+        ReplaceMode::AllOccurrences,
+    )
+}
+
+/* Same as detect_sql_injection_str, but when the user input occurs more than once in the query,
+ * every occurrence is replaced and checked on its own. The `span` of the result is the
+ * occurrence that altered the structure of the query, e.g. for
+ * `SELECT * FROM users WHERE users = 'users' OR 1=1` with user input `users' OR 1=1` only the
+ * second occurrence (34..47) is an injection.
+ */
+pub fn detect_sql_injection_per_occurrence(
+    query_raw: &str,
+    userinput_raw: &str,
+    dialect: i32,
+) -> SqlInjectionDetectionResult {
+    let query = CaseInsensitiveStr::new(query_raw);
+
+    detect_sql_injection_in_query(
+        &query,
+        userinput_raw,
+        dialect,
+        &mut None,
+        ReplaceMode::EachOccurrence,
+    )
+}
+
+/* Same as detect_sql_injection_str, but if the user input is not found in the query, the given
+ * decodings of the user input are tried (in order) as well. Frameworks sometimes transform the
+ * user input before it ends up in the query, e.g. `%27%20OR%201%3D1` is URL-decoded to `' OR 1=1`.
+ * The `decoding` of the result is the decoding that was found in the query.
+ */
+pub fn detect_sql_injection_with_decodings(
+    query_raw: &str,
+    userinput_raw: &str,
+    dialect: i32,
+    decodings: &[UserInputDecoding],
+) -> SqlInjectionDetectionResult {
+    let query = CaseInsensitiveStr::new(query_raw);
+    let mut tokens: Option<Vec<Token>> = None;
+
+    let detection_results = detect_sql_injection_in_query(
+        &query,
+        userinput_raw,
+        dialect,
+        &mut tokens,
+        ReplaceMode::AllOccurrences,
+    );
+    if !matches!(
+        detection_results.reason,
+        DetectionReason::UserInputNotInQuery
+    ) {
+        return detection_results;
+    }
+
+    for &decoding in decodings {
+        let decoded_userinput = match decoding.decode(userinput_raw) {
+            Some(decoded_userinput) if decoded_userinput != userinput_raw => decoded_userinput,
+            // The decoding failed or did not change the user input.
+            _ => continue,
+        };
+
+        let decoded_detection_results = detect_sql_injection_in_query(
+            &query,
+            &decoded_userinput,
+            dialect,
+            &mut tokens,
+            ReplaceMode::AllOccurrences,
+        );
+        if !matches!(
+            decoded_detection_results.reason,
+            DetectionReason::UserInputNotInQuery
+        ) {
+            return SqlInjectionDetectionResult {
+                decoding: Some(decoding),
+                ..decoded_detection_results
+            };
+        }
+    }
+
+    detection_results
+}
+
+/* Checks a list of user inputs against the same query, e.g. all query parameters, headers and
+ * cookies of a request. Gives the same results as calling detect_sql_injection_str for each
+ * user input, but the query is only lowercased once, and tokenized once (only if one of the user
+ * inputs requires it). The user inputs are searched for in the same lowercased query.
+ */
+pub fn detect_sql_injection_batch(
+    query_raw: &str,
+    userinputs_raw: &[&str],
+    dialect: i32,
+) -> Vec<SqlInjectionDetectionResult> {
+    let query = CaseInsensitiveStr::new(query_raw);
+    let mut tokens: Option<Vec<Token>> = None;
+
+    userinputs_raw
+        .iter()
+        .map(|userinput_raw| {
+            detect_sql_injection_in_query(
+                &query,
+                userinput_raw,
+                dialect,
+                &mut tokens,
+                ReplaceMode::AllOccurrences,
+            )
+        })
+        .collect()
+}
+
+/* Same as detect_sql_injection_str, but the query is already lowercased and `tokens` are the
+ * tokens of the query if they are already known (e.g. cached by SqlInjectionDetector). If the query
+ * had to be tokenized, its tokens are stored in `tokens` so they can be reused for the next call
+ * with the same query and dialect.
+ */
+pub fn detect_sql_injection_with_tokens(
+    query: &CaseInsensitiveStr,
+    userinput_raw: &str,
+    dialect: i32,
+    tokens: &mut Option<Vec<Token>>,
+) -> SqlInjectionDetectionResult {
+    detect_sql_injection_in_query(
+        query,
+        userinput_raw,
+        dialect,
+        tokens,
+        ReplaceMode::AllOccurrences,
+    )
+}
+
+/* Same as detect_sql_injection_str, but for huge queries (e.g. bulk INSERTs with megabytes of
+ * VALUES) : if the query is longer than `max_len` bytes, only the statement(s) around every
+ * occurrence of the user input are checked, instead of lowercasing and tokenizing the whole query.
+ * If such a statement is longer than `max_len` bytes as well, it's not checked and the reason is
+ * QueryTooLarge (unless another occurrence is an injection), so the latency stays predictable.
+ */
+pub fn detect_sql_injection_with_max_len(
+    query_raw: &str,
+    userinput_raw: &str,
+    dialect: i32,
+    max_len: usize,
+) -> SqlInjectionDetectionResult {
+    if query_raw.len() <= max_len {
+        return detect_sql_injection_str(query_raw, userinput_raw, dialect);
+    }
+
+    let occurrences = find_case_insensitive(query_raw, userinput_raw);
+    let Some(first_occurrence) = occurrences.first() else {
+        return SqlInjectionDetectionResult {
+            detected: false,
+            reason: DetectionReason::UserInputNotInQuery,
+            span: None,
+            decoding: None,
+            exemption: None,
+        };
+    };
+
+    let statements = split_statements(query_raw, dialect);
+    let mut first_results: Option<SqlInjectionDetectionResult> = None;
+    let mut too_large_results: Option<SqlInjectionDetectionResult> = None;
+    let mut previous_window: Option<Range<usize>> = None;
+
+    for occurrence in &occurrences {
+        // The statements that overlap with the occurrence, the user input can contain semicolons.
+        let window = statements
+            .iter()
+            .filter(|statement| {
+                statement.start <= occurrence.end && occurrence.start <= statement.end
+            })
+            .fold(occurrence.clone(), |window, statement| {
+                window.start.min(statement.start)..window.end.max(statement.end)
+            });
+        if previous_window.as_ref() == Some(&window) {
+            // All the occurrences in a window are replaced at once.
+            continue;
+        }
+        previous_window = Some(window.clone());
+
+        if window.len() > max_len {
+            too_large_results.get_or_insert(SqlInjectionDetectionResult {
+                detected: false,
+                reason: DetectionReason::QueryTooLarge,
+                span: Some(occurrence.clone()),
+                decoding: None,
+                exemption: None,
+            });
+            continue;
+        }
+
+        let window_results =
+            detect_sql_injection_str(&query_raw[window.clone()], userinput_raw, dialect);
+        let window_results = SqlInjectionDetectionResult {
+            span: window_results
+                .span
+                .map(|span| span.start + window.start..span.end + window.start),
+            ..window_results
+        };
+
+        match window_results.reason {
+            // These only depend on the user input, no need to check the other windows. An ORDER BY
+            // list depends on the query : the other occurrences have to follow ORDER BY as well.
+            DetectionReason::CommonSQLString if !is_order_by_exemption(&window_results) => {
+                return window_results;
+            }
+            DetectionReason::UserInputTooSmall => return window_results,
+            _ if window_results.detected => return window_results,
+            _ => {
+                first_results.get_or_insert(window_results);
+            }
+        }
+    }
+
+    // Not every occurrence was checked if one of the statements was too large.
+    too_large_results
+        .or(first_results)
+        .unwrap_or(SqlInjectionDetectionResult {
+            detected: false,
+            reason: DetectionReason::NoChangesFound,
+            span: Some(first_occurrence.clone()),
+            decoding: None,
+            exemption: None,
+        })
+}
+
+fn is_order_by_exemption(detection_results: &SqlInjectionDetectionResult) -> bool {
+    matches!(
+        detection_results.exemption,
+        Some(SqlExemption {
+            kind: SqlExemptionKind::OrderByList,
+            ..
+        })
+    )
+}
+
+/* The dialects that are tried by detect_sql_injection_auto_dialect, for when the dialect of the
+ * database is not known (e.g. the ORM or driver does not expose it). Every dialect here has its
+ * own tokenization rules (quoting, escaping, comments, ...), MariaDB is left out as it uses the
+ * MySQL tokenizer.
+ */
+pub const AUTO_DIALECT_CANDIDATES: [SqlDialect; 6] = [
+    SqlDialect::Generic,
+    SqlDialect::MySql,
+    SqlDialect::PostgreSql,
+    SqlDialect::MsSql,
+    SqlDialect::SQLite,
+    SqlDialect::Oracle,
+];
+
+#[derive(Debug, Serialize)]
+pub struct SqlInjectionAutoDialectResult {
+    pub detected: bool,
+    pub reason: DetectionReason,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Range<usize>>,
+    // The dialect in which the injection was detected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<SqlDialect>,
+    // The dialects in which the query was tokenized and checked, in order.
+    pub dialects_evaluated: Vec<SqlDialect>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exemption: Option<SqlExemption>,
+}
+
+/* Same as detect_sql_injection_str, but the query is checked with every dialect of
+ * AUTO_DIALECT_CANDIDATES. Dialects in which the original query fails to tokenize are skipped,
+ * it's an injection as soon as one of the other dialects finds a change in the structure of the
+ * query.
+ */
+pub fn detect_sql_injection_auto_dialect(
+    query_raw: &str,
+    userinput_raw: &str,
+) -> SqlInjectionAutoDialectResult {
+    let query = CaseInsensitiveStr::new(query_raw);
+    let mut dialects_evaluated: Vec<SqlDialect> = Vec::new();
+    let mut span: Option<Range<usize>> = None;
+
+    for dialect in AUTO_DIALECT_CANDIDATES {
+        let detection_results = detect_sql_injection_in_query(
+            &query,
+            userinput_raw,
+            dialect as i32,
+            &mut None,
+            ReplaceMode::AllOccurrences,
+        );
+
+        match detection_results.reason {
+            // These don't depend on the dialect, the query did not need to be tokenized.
+            DetectionReason::UserInputNotInQuery
+            | DetectionReason::CommonSQLString
+            | DetectionReason::UserInputTooSmall => {
+                return SqlInjectionAutoDialectResult {
+                    detected: false,
+                    reason: detection_results.reason,
+                    span: detection_results.span,
+                    dialect: None,
+                    dialects_evaluated,
+                    exemption: detection_results.exemption,
+                };
+            }
+            // The query is not valid in this dialect, so it's probably not the dialect in use.
+            DetectionReason::FailedToTokenizeQuery => continue,
+            _ => {}
+        }
+
+        dialects_evaluated.push(dialect);
+        if detection_results.detected {
+            return SqlInjectionAutoDialectResult {
+                detected: true,
+                reason: detection_results.reason,
+                span: detection_results.span,
+                dialect: Some(dialect),
+                dialects_evaluated,
+                exemption: None,
+            };
+        }
+        span = detection_results.span;
+    }
+
+    SqlInjectionAutoDialectResult {
+        detected: false,
+        reason: if dialects_evaluated.is_empty() {
+            DetectionReason::FailedToTokenizeQuery
+        } else {
+            DetectionReason::NoChangesFound
+        },
+        span,
+        dialect: None,
+        dialects_evaluated,
+        exemption: None,
+    }
+}
+
+/* The user input is matched case-insensitively (using the lowercased `query`), but the query is
+ * tokenized in its original case : some constructs are case-sensitive, e.g. the PostgreSQL
+ * dollar-quoted string `$Tag$...$Tag$` is not closed by `$tag$`.
+ * `tokens` caches the tokens of the original query so they can be reused for other user inputs.
+ */
+fn detect_sql_injection_in_query(
+    query: &CaseInsensitiveStr,
+    userinput_raw: &str,
+    dialect: i32,
+    tokens: &mut Option<Vec<Token>>,
+    replace_mode: ReplaceMode,
+) -> SqlInjectionDetectionResult {
+    let query_raw = query.original();
     let userinput: String = userinput_raw.to_lowercase();
 
-    if !query.contains(&userinput) {
+    let Some(first_occurrence) = query.find_first(&userinput) else {
         // If the query does not contain the user input, it's not an injection.
         return SqlInjectionDetectionResult {
             detected: false,
             reason: DetectionReason::UserInputNotInQuery,
+            span: None,
+            decoding: None,
+            exemption: None,
         };
-    }
+    };
+    let span = Some(first_occurrence);
 
     // "SELECT *", "INSERT INTO", ... will occur in most queries
     // If the user input is equal to any of these, we can assume it's not an injection.
-    if is_common_sql_string(&userinput) {
+    if let Some(exemption) = find_exemption(&userinput) {
         return SqlInjectionDetectionResult {
             detected: false,
             reason: DetectionReason::CommonSQLString,
+            span,
+            decoding: None,
+            exemption: Some(exemption),
         };
     }
 
@@ -58,45 +443,134 @@ pub fn detect_sql_injection_str(
         return SqlInjectionDetectionResult {
             detected: false,
             reason: DetectionReason::UserInputTooSmall,
+            span,
+            decoding: None,
+            exemption: None,
+        };
+    }
+
+    // e.g. `name asc, created_at desc` as the ORDER BY clause, it can only change the sort order.
+    if query.lowercased().contains("order")
+        && is_order_by_list_in_query(query, userinput_raw, dialect)
+    {
+        return SqlInjectionDetectionResult {
+            detected: false,
+            reason: DetectionReason::CommonSQLString,
+            span,
+            decoding: None,
+            exemption: Some(SqlExemption::order_by_list(userinput_raw)),
         };
     }
 
     // Tokenize query :
-    let tokens = tokenize_query(&query, dialect);
+    let tokens = tokens.get_or_insert_with(|| tokenize_query(query_raw, dialect));
     if tokens.is_empty() {
         // Tokens are empty, probably a parsing issue with original query, return false.
         return SqlInjectionDetectionResult {
             detected: false,
             reason: DetectionReason::FailedToTokenizeQuery,
+            span,
+            decoding: None,
+            exemption: None,
+        };
+    }
+
+    if let ReplaceMode::EachOccurrence = replace_mode {
+        // Replace every occurrence on its own, so that a harmless occurrence (e.g. a column name
+        // that happens to match) can't mask or cause a change in the structure of the query.
+        let trimmed_userinput_raw = userinput_raw.trim_matches(SPACE_CHAR);
+        for occurrence in query.find_all(trimmed_userinput_raw) {
+            let mut query_without_occurrence = query_raw.to_string();
+            query_without_occurrence
+                .replace_range(occurrence.clone(), &"a".repeat(occurrence.len()));
+            let tokens_without_input = tokenize_query(&query_without_occurrence, dialect);
+
+            if let Some(reason) = compare_tokens(tokens, &tokens_without_input, dialect) {
+                return SqlInjectionDetectionResult {
+                    detected: true,
+                    reason,
+                    span: Some(occurrence),
+                    decoding: None,
+                    exemption: None,
+                };
+            }
+        }
+
+        return SqlInjectionDetectionResult {
+            detected: false,
+            reason: DetectionReason::NoChangesFound,
+            span,
+            decoding: None,
+            exemption: None,
         };
     }
 
     // Replace user input with string of equal length and tokenize again :
-    let safe_replace_str = "a".repeat(trimmed_userinput.len());
-    let query_without_input: &str = &query.replace(trimmed_userinput, safe_replace_str.as_str());
-    let tokens_without_input = tokenize_query(query_without_input, dialect);
+    let query_without_input = replace_user_input_with_safe_str(query, userinput_raw);
+    let tokens_without_input = tokenize_query(&query_without_input, dialect);
 
+    match compare_tokens(tokens, &tokens_without_input, dialect) {
+        Some(reason) => SqlInjectionDetectionResult {
+            detected: true,
+            reason,
+            span,
+            decoding: None,
+            exemption: None,
+        },
+        None => SqlInjectionDetectionResult {
+            detected: false,
+            reason: DetectionReason::NoChangesFound,
+            span,
+            decoding: None,
+            exemption: None,
+        },
+    }
+}
+
+// Returns the reason for the injection if the structure of the query changed.
+fn compare_tokens(
+    tokens: &[Token],
+    tokens_without_input: &[Token],
+    dialect: i32,
+) -> Option<DetectionReason> {
     // Check delta for both comment tokens and all tokens in general :
     if diff_in_vec_len!(tokens, tokens_without_input) {
         // If a delta exists in all tokens, mark this as an injection.
-        return SqlInjectionDetectionResult {
-            detected: true,
-            reason: DetectionReason::TokensHaveDelta,
-        };
+        return Some(DetectionReason::TokensHaveDelta);
     }
 
-    if have_comments_changed(tokens, tokens_without_input) {
+    if have_comments_changed(tokens, tokens_without_input, dialect) {
         // This checks if structure of comments in the query is altered after removing user input.
         // It makes sure the lengths of all single line and multiline comments are all still the same
         // And makes sure no extra comments were added or that the order was altered.
-        return SqlInjectionDetectionResult {
-            detected: true,
-            reason: DetectionReason::CommentStructureAltered,
-        };
+        return Some(DetectionReason::CommentStructureAltered);
     }
 
-    SqlInjectionDetectionResult {
-        detected: false,
-        reason: DetectionReason::NoChangesFound,
+    if have_token_categories_changed(tokens, tokens_without_input) {
+        // The amount of tokens is the same, but e.g. a keyword became a semicolon. This can
+        // happen when a crafted user input moves the boundaries of strings in the query.
+        return Some(DetectionReason::TokenKindsChanged);
     }
+
+    None
+}
+
+/* Replaces every occurrence of the user input (ignoring case) in the query with a string of equal
+ * length that can't alter the structure of the query, e.g. with user input `1 OR 1=1` :
+ * `SELECT * FROM users WHERE id = 1 or 1=1` -> `SELECT * FROM users WHERE id = aaaaaaaa`
+ */
+pub fn replace_user_input_with_safe_str(query: &CaseInsensitiveStr, userinput_raw: &str) -> String {
+    let query_raw = query.original();
+    let trimmed_userinput_raw = userinput_raw.trim_matches(SPACE_CHAR);
+
+    let mut query_without_input = String::with_capacity(query_raw.len());
+    let mut last_end = 0;
+    for occurrence in query.find_all(trimmed_userinput_raw) {
+        query_without_input.push_str(&query_raw[last_end..occurrence.start]);
+        query_without_input.push_str(&"a".repeat(occurrence.len()));
+        last_end = occurrence.end;
+    }
+    query_without_input.push_str(&query_raw[last_end..]);
+
+    query_without_input
 }
@@ -1,6 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use crate::sql_injection::detect_sql_injection::detect_sql_injection_str;
+    use crate::sql_injection::custom_exemptions::{SqlExemption, SqlExemptionKind};
+    use crate::sql_injection::detect_sql_injection::{
+        detect_sql_injection_auto_dialect, detect_sql_injection_batch,
+        detect_sql_injection_per_occurrence, detect_sql_injection_str,
+        detect_sql_injection_with_decodings, detect_sql_injection_with_max_len, DetectionReason,
+        AUTO_DIALECT_CANDIDATES,
+    };
+    use crate::sql_injection::sql_dialect::SqlDialect;
+    use crate::sql_injection::user_input_decoding::UserInputDecoding;
 
     fn dialect(s: &str) -> i32 {
         match s {
@@ -9,6 +17,9 @@ mod tests {
             "sqlite" => 12,
             "clickhouse" => 3,
             "generic" => 0,
+            "oracle" => 13,
+            "mariadb" => 14,
+            "mssql" => 7,
             _ => panic!("Unknown dialect"),
         }
     }
@@ -20,6 +31,8 @@ mod tests {
             dialect("sqlite"),
             dialect("clickhouse"),
             dialect("generic"),
+            dialect("oracle"),
+            dialect("mariadb"),
         ]
     }
 
@@ -94,6 +107,288 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_oracle_quote_delimited_strings() {
+        not_injection!(
+            "SELECT * FROM users WHERE name = q'[O'Brien]'",
+            "O'Brien",
+            dialect("oracle")
+        );
+        not_injection!(
+            "SELECT * FROM users WHERE name = q'[' OR 1=1 -- ]'",
+            "' OR 1=1 -- ",
+            dialect("oracle")
+        );
+        not_injection!(
+            "SELECT * FROM users WHERE name = Q'{' OR 1=1 -- }'",
+            "' OR 1=1 -- ",
+            dialect("oracle")
+        );
+        // MySQL does not know q-quote strings, `q'[' OR 1=1 -- ]'` is `q`, `'['` and a comment.
+        is_injection!(
+            "SELECT * FROM users WHERE name = q'[' OR 1=1 -- ]'",
+            "' OR 1=1 -- ",
+            dialect("mysql")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE name = q'[a]' OR 1=1 --]'",
+            "a]' OR 1=1 --",
+            dialect("oracle")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE name = q'{a}' OR q'{b}'",
+            "a}' OR q'{b",
+            dialect("oracle")
+        );
+    }
+
+    #[test]
+    fn test_mariadb_executable_comments() {
+        // The content of `/*! ... */` is executed by MySQL and MariaDB.
+        is_injection!(
+            "SELECT * FROM users WHERE id = 1 /*! OR 1=1 */",
+            "1 /*! OR 1=1 */",
+            dialect("mariadb")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE id = 1 /*!50000 OR 1=1 */",
+            "OR 1=1",
+            dialect("mariadb")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE name = 'a' /*! OR 1=1 */",
+            "a' /*! OR 1=1 */",
+            dialect("mariadb")
+        );
+        not_injection!(
+            "SELECT * FROM users WHERE name = '/*! OR 1=1 */'",
+            "/*! OR 1=1 */",
+            dialect("mariadb")
+        );
+        // For other dialects it's a regular comment.
+        not_injection!(
+            "SELECT * FROM users WHERE id = 1 /*!50000 OR 1=1 */",
+            "OR 1=1",
+            dialect("sqlite")
+        );
+    }
+
+    #[test]
+    fn test_mysql_optimizer_hints_and_executable_comments() {
+        // The user input turns a regular comment into an optimizer hint, the length is the same.
+        is_injection!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1) */ * FROM users WHERE id = 1",
+            "+ MAX_EXECUTION_TIME(1)",
+            dialect("mysql")
+        );
+        // The user input modifies the structure of an optimizer hint.
+        is_injection!(
+            "SELECT /*+ INDEX(users a) BKA(users) */ * FROM users WHERE id = 1",
+            "a) BKA(users",
+            dialect("mysql")
+        );
+        // The user input is only a name inside the optimizer hint.
+        not_injection!(
+            "SELECT /*+ INDEX(users idx_name) */ * FROM users WHERE id = 1",
+            "idx_name",
+            dialect("mysql")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE id = 1 /*+ BKA(users) */",
+            "1 /*+ BKA(users) */",
+            dialect("mysql")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE id = 1 /*!50000 OR 1=1 */",
+            "!50000 OR 1=1",
+            dialect("mysql")
+        );
+        // For other dialects these are regular comments of the same length.
+        not_injection!(
+            "SELECT * FROM users WHERE id = 1 /*! OR 1=1 */",
+            "! OR 1=1",
+            dialect("sqlite")
+        );
+        not_injection!(
+            "SELECT /*+ INDEX(users idx_name) */ * FROM users WHERE id = 1",
+            "idx_name",
+            dialect("postgresql")
+        );
+        // Oracle interprets optimizer hints as well.
+        is_injection!(
+            "SELECT /*+ INDEX(users a) FULL(users) */ * FROM users WHERE id = 1",
+            "a) FULL(users",
+            dialect("oracle")
+        );
+        not_injection!(
+            "SELECT /*+ INDEX(users idx_name) */ * FROM users WHERE id = 1",
+            "idx_name",
+            dialect("oracle")
+        );
+
+        not_injection!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users WHERE name = 'hello world'",
+            "hello world",
+            dialect("mysql")
+        );
+        not_injection!(
+            "SELECT * FROM users WHERE name = '/*+ BKA(users) */'",
+            "/*+ BKA(users) */",
+            dialect("mysql")
+        );
+        not_injection!(
+            "SELECT * FROM users WHERE name = '/*!50000 OR 1=1 */'",
+            "/*!50000 OR 1=1 */",
+            dialect("mysql")
+        );
+    }
+
+    #[test]
+    fn test_token_kinds_changed_with_same_amount_of_tokens() {
+        // MySQL requires a space after `--` for a comment, so `------` are minus signs. Replacing
+        // both occurrences gives the same amount of tokens, but the semicolon disappears.
+        let query = "SELECT * FROM users WHERE name = '';------' AND email = '';------'";
+        let result = detect_sql_injection_str(query, "';------", dialect("mysql"));
+        assert!(result.detected);
+        assert!(matches!(result.reason, DetectionReason::TokenKindsChanged));
+    }
+
+    #[test]
+    fn test_token_kinds_no_false_positives_corpus() {
+        // Combinations of characters that can't alter the structure of the query when they are
+        // inside of a string literal.
+        let fragments = [
+            "hello", " ", "-", "--", ";", "/", "*", ",", ".", "(", ")", "=", "#", "@", "!", "?",
+            "%", "1", "+", "<", ">", "|", "&", ":", "[", "]", "{", "}", "~", "^",
+        ];
+        let templates = [
+            "SELECT * FROM users WHERE name = '{}'",
+            "SELECT * FROM users WHERE bio LIKE '%{}%' AND id = 1",
+            "INSERT INTO users (name, bio) VALUES ('{}', 'bio')",
+            "UPDATE users SET bio = '{}' WHERE id = 1",
+        ];
+
+        for fragment1 in fragments {
+            for fragment2 in fragments {
+                for fragment3 in ["hello", ";"] {
+                    let userinput = format!("{}{}{}", fragment1, fragment2, fragment3);
+                    for template in templates {
+                        not_injection!(&template.replace("{}", &userinput), &userinput);
+                    }
+                }
+            }
+        }
+
+        // User input that is a whole identifier, number, keyword operator or LIMIT value.
+        let corpus = [
+            (
+                "SELECT {} FROM users WHERE id = 1",
+                vec!["name", "users.name", "created_at"],
+            ),
+            (
+                "SELECT * FROM {} WHERE id = 1",
+                vec!["users", "public.users", "user_accounts"],
+            ),
+            (
+                "SELECT * FROM users WHERE {} = 1",
+                vec!["id", "users.id", "tenant_id", "is_active"],
+            ),
+            (
+                "SELECT * FROM users WHERE id = {} AND active = 1",
+                vec!["12", "123456", "1.5", "0.001", "1e10", "0x1f"],
+            ),
+            (
+                "SELECT * FROM users WHERE name {} 'john%'",
+                vec!["like", "ilike", "LIKE"],
+            ),
+            (
+                "SELECT * FROM users ORDER BY name LIMIT {}",
+                vec!["10", "100", "5000"],
+            ),
+            (
+                "SELECT * FROM users ORDER BY name LIMIT 10 OFFSET {}",
+                vec!["20", "1000"],
+            ),
+        ];
+
+        for (template, userinputs) in corpus {
+            for userinput in userinputs {
+                not_injection!(&template.replace("{}", userinput), userinput);
+            }
+        }
+    }
+
+    #[test]
+    fn test_operator_user_input_is_not_flagged() {
+        // The user input is an operator of the query (e.g. `>=` picked from a filter UI). The
+        // operator becomes an identifier, but the rest of the query is not altered.
+        for operator in [">=", "<=", "<>", "!="] {
+            let query = format!("SELECT * FROM users WHERE age {} 18", operator);
+            for dia in get_supported_dialects() {
+                let result = detect_sql_injection_str(&query, operator, dia);
+                assert!(!result.detected);
+                assert!(matches!(result.reason, DetectionReason::NoChangesFound));
+            }
+        }
+        is_injection!(
+            "SELECT * FROM users WHERE age >= 18; DROP TABLE users",
+            ">= 18; DROP TABLE users"
+        );
+
+        // A single character is too small to be an injection.
+        not_injection!("SELECT * FROM users WHERE age > 18", ">");
+    }
+
+    #[test]
+    fn test_postgres_mixed_case_dollar_tags() {
+        // Dollar-quote tags are case-sensitive, `$X$` does not close `$x$`.
+        not_injection!(
+            "SELECT * FROM users WHERE id = $x$foo$X$ OR 1=1 --$x$",
+            "foo$X$ OR 1=1 --",
+            dialect("postgresql")
+        );
+        not_injection!(
+            "SELECT * FROM users WHERE id = $Tag$foo$tag$ OR 1=1 --$Tag$",
+            "foo$tag$ OR 1=1 --",
+            dialect("postgresql")
+        );
+        is_injection!(
+            "SELECT * FROM users WHERE id = $Tag$1$Tag$ OR 1=1 -- $Tag$",
+            "1$Tag$ OR 1=1 -- ",
+            dialect("postgresql")
+        );
+        // The user input is still matched case-insensitively.
+        is_injection!(
+            "SELECT * FROM users WHERE id = $Tag$1$Tag$ OR 1=1 -- $Tag$",
+            "1$tag$ or 1=1 -- ",
+            dialect("postgresql")
+        );
+    }
+
+    #[test]
+    fn test_mixed_case_quoted_identifiers() {
+        not_injection!(
+            "SELECT \"UserName\" FROM \"Users\" WHERE id = 1",
+            "username",
+            dialect("postgresql")
+        );
+        not_injection!(
+            "SELECT * FROM \"Users\" WHERE \"Name\" = 'x\" OR \"y'",
+            "X\" or \"Y",
+            dialect("postgresql")
+        );
+        is_injection!(
+            "SELECT * FROM \"Users\" WHERE \"Name\" = '' OR 1=1 -- '",
+            "' or 1=1 -- ",
+            dialect("postgresql")
+        );
+        is_injection!(
+            "SELECT * FROM \"Users\" ORDER BY \"Name\", \"Id\"; DROP TABLE \"Users\"",
+            "name\", \"id\"; drop table \"users",
+            dialect("postgresql")
+        );
+    }
+
     #[test]
     fn test_it_detects_injections() {
         is_injection!(
@@ -486,11 +781,51 @@ mod tests {
           "date DESC LIMIT 1"
         );
         is_injection!(
+          "select `recommendations`.*, (select count(*) from `recommendation_click_events` where `recommendation_click_events`.`recommendation_id` = recommendations.id) as `count__clicks`, (select count(*) from `recommendation_subscribe_events` where `recommendation_subscribe_events`.`recommendation_id` = recommendations.id) as `count__subscribers` from `recommendations` order by date DESC, id ASC limit 1",
+          "date DESC, id ASC limit 1"
+        );
+        // A sort list in the ORDER BY clause can only change the sort order.
+        not_injection!(
           "select `recommendations`.*, (select count(*) from `recommendation_click_events` where `recommendation_click_events`.`recommendation_id` = recommendations.id) as `count__clicks`, (select count(*) from `recommendation_subscribe_events` where `recommendation_subscribe_events`.`recommendation_id` = recommendations.id) as `count__subscribers` from `recommendations` order by date DESC, id ASC limit 1",
           "date DESC, id ASC"
         );
     }
 
+    #[test]
+    fn test_order_by_list_exemption() {
+        let query = "SELECT * FROM posts ORDER BY name asc, created_at desc LIMIT 10";
+        for dia in get_supported_dialects() {
+            let result = detect_sql_injection_str(query, "name asc, created_at desc", dia);
+            assert!(!result.detected);
+            assert!(matches!(result.reason, DetectionReason::CommonSQLString));
+            assert_eq!(
+                result.exemption,
+                Some(SqlExemption {
+                    kind: SqlExemptionKind::OrderByList,
+                    rule: "name asc, created_at desc".to_string(),
+                })
+            );
+        }
+
+        not_injection!(
+            "SELECT * FROM posts p ORDER BY p.published_at DESC NULLS LAST, p.id",
+            "p.published_at DESC NULLS LAST, p.id"
+        );
+        is_injection!(
+            "SELECT * FROM posts ORDER BY name asc, (SELECT password FROM users LIMIT 1)",
+            "name asc, (SELECT password FROM users LIMIT 1)"
+        );
+        is_injection!(
+            "SELECT * FROM posts ORDER BY name asc -- , id LIMIT 10",
+            "name asc -- , id"
+        );
+        // The sort list is not used in the ORDER BY clause.
+        is_injection!(
+            "SELECT name asc, created_at desc FROM posts",
+            "name asc, created_at desc"
+        );
+    }
+
     #[test]
     fn test_postgres_backslash_escaping() {
         is_injection!(
@@ -1171,4 +1506,465 @@ mod tests {
             "TIME ZONE 'UTC'"
         );
     }
+
+    #[test]
+    fn test_detection_result_details() {
+        let query = "SELECT * FROM users WHERE id = '' OR 1=1 -- '";
+        let result = detect_sql_injection_str(query, "' OR 1=1 -- ", dialect("postgresql"));
+        assert_eq!(result.span, Some(32..44));
+        assert_eq!(&query[32..44], "' OR 1=1 -- ");
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "detected": true,
+                "reason": "TokensHaveDelta",
+                "span": { "start": 32, "end": 44 }
+            })
+        );
+
+        let result = detect_sql_injection_str(query, "nope", dialect("postgresql"));
+        assert_eq!(result.span, None);
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({ "detected": false, "reason": "UserInputNotInQuery" })
+        );
+    }
+
+    #[test]
+    fn test_detection_result_span_is_case_insensitive() {
+        let query = "SELECT * FROM users WHERE name = 'JOHN'";
+        let result = detect_sql_injection_str(query, "john", dialect("postgresql"));
+        assert_eq!(result.span, Some(34..38));
+    }
+
+    #[test]
+    fn test_batch_gives_same_results_as_single_checks() {
+        let query =
+            "SELECT * FROM users WHERE id = '' OR 1=1 -- ' AND name = 'John' ORDER BY name ASC";
+        let userinputs = ["' OR 1=1 -- ", "John", "name asc", "not in query", "a", ""];
+
+        for dia in get_supported_dialects() {
+            let results = detect_sql_injection_batch(query, &userinputs, dia);
+            assert_eq!(results.len(), userinputs.len());
+            for (userinput, result) in userinputs.iter().zip(results) {
+                let expected = detect_sql_injection_str(query, userinput, dia);
+                assert_eq!(
+                    serde_json::to_value(&result).unwrap(),
+                    serde_json::to_value(&expected).unwrap(),
+                    "user input: {}",
+                    userinput
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_with_query_that_fails_to_tokenize() {
+        let results = detect_sql_injection_batch(
+            "SELECT unicorns fly over the \"rainbow",
+            &["rainbow", "unicorns"],
+            dialect("generic"),
+        );
+        assert!(results.iter().all(|result| !result.detected));
+        assert_eq!(
+            serde_json::to_value(&results).unwrap(),
+            serde_json::json!([
+                { "detected": false, "reason": "FailedToTokenizeQuery", "span": { "start": 30, "end": 37 } },
+                { "detected": false, "reason": "FailedToTokenizeQuery", "span": { "start": 7, "end": 15 } }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_batch_without_user_inputs() {
+        assert!(detect_sql_injection_batch("SELECT 1", &[], dialect("generic")).is_empty());
+    }
+    #[test]
+    fn test_per_occurrence_reports_offending_occurrence() {
+        let query = "SELECT * FROM users WHERE name = '1 OR 1=1' OR id = 1 OR 1=1";
+        for dia in get_supported_dialects() {
+            let result = detect_sql_injection_per_occurrence(query, "1 OR 1=1", dia);
+            assert!(result.detected);
+            assert_eq!(result.span, Some(52..60));
+            assert_eq!(&query[52..60], "1 OR 1=1");
+
+            // Replacing all occurrences at once only reports the first one.
+            assert_eq!(
+                detect_sql_injection_str(query, "1 OR 1=1", dia).span,
+                Some(34..42)
+            );
+        }
+    }
+
+    #[test]
+    fn test_per_occurrence_case_insensitive() {
+        let result = detect_sql_injection_per_occurrence(
+            "SELECT * FROM users WHERE name = 'admin' OR id = 1 oR 1=1",
+            "1 Or 1=1",
+            dialect("postgresql"),
+        );
+        assert!(result.detected);
+        assert_eq!(result.span, Some(49..57));
+    }
+
+    #[test]
+    fn test_per_occurrence_without_injection() {
+        let query = "SELECT * FROM users WHERE name = 'O''Brien' AND note = 'O''Brien'";
+        for dia in get_supported_dialects() {
+            let result = detect_sql_injection_per_occurrence(query, "O''Brien", dia);
+            assert!(!result.detected);
+            assert_eq!(result.span, Some(34..42));
+        }
+
+        let result = detect_sql_injection_per_occurrence(query, "nope", dialect("mysql"));
+        assert!(!result.detected);
+        assert_eq!(result.span, None);
+    }
+
+    #[test]
+    fn test_decodings_of_user_input() {
+        let all_decodings = [
+            UserInputDecoding::UrlDecode,
+            UserInputDecoding::HtmlEntityDecode,
+            UserInputDecoding::JsonUnescape,
+            UserInputDecoding::BackslashEscapeQuotes,
+        ];
+        let cases = [
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "%27%20OR%201%3D1%20--%20",
+                UserInputDecoding::UrlDecode,
+            ),
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "&#39; OR 1=1 -- ",
+                UserInputDecoding::HtmlEntityDecode,
+            ),
+            (
+                "SELECT * FROM users WHERE name = \"\" OR \"a\"=\"a\"",
+                r#"\" OR \"a\"=\"a"#,
+                UserInputDecoding::JsonUnescape,
+            ),
+        ];
+
+        for (query, userinput, decoding) in cases {
+            let result = detect_sql_injection_str(query, userinput, dialect("mysql"));
+            assert!(!result.detected);
+            assert!(result.decoding.is_none());
+
+            // Only the enabled decodings are tried.
+            let result =
+                detect_sql_injection_with_decodings(query, userinput, dialect("mysql"), &[]);
+            assert!(!result.detected);
+
+            let result = detect_sql_injection_with_decodings(
+                query,
+                userinput,
+                dialect("mysql"),
+                &all_decodings,
+            );
+            assert!(result.detected, "query: {}\ninput: {}", query, userinput);
+            assert_eq!(result.decoding, Some(decoding));
+        }
+
+        // The escaped user input is found in the query, but the escaping prevents the injection.
+        let result = detect_sql_injection_with_decodings(
+            "SELECT * FROM users WHERE id = '\\' OR \\'1\\'=\\'1'",
+            "' OR '1'='1",
+            dialect("mysql"),
+            &all_decodings,
+        );
+        assert!(!result.detected);
+        assert!(matches!(result.reason, DetectionReason::NoChangesFound));
+        assert_eq!(
+            result.decoding,
+            Some(UserInputDecoding::BackslashEscapeQuotes)
+        );
+    }
+
+    #[test]
+    fn test_decodings_not_tried_if_user_input_in_query() {
+        let result = detect_sql_injection_with_decodings(
+            "SELECT * FROM users WHERE name = 'a%20b'",
+            "a%20b",
+            dialect("postgresql"),
+            &[UserInputDecoding::UrlDecode],
+        );
+        assert!(!result.detected);
+        assert_eq!(result.decoding, None);
+        assert_eq!(result.span, Some(34..39));
+
+        let result = detect_sql_injection_with_decodings(
+            "SELECT * FROM users WHERE name = 'hello world'",
+            "hello%20world",
+            dialect("postgresql"),
+            &[UserInputDecoding::UrlDecode],
+        );
+        assert!(!result.detected);
+        assert_eq!(result.decoding, Some(UserInputDecoding::UrlDecode));
+        assert_eq!(result.span, Some(34..45));
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "detected": false,
+                "reason": "NoChangesFound",
+                "span": { "start": 34, "end": 45 },
+                "decoding": "UrlDecode"
+            })
+        );
+    }
+
+    #[test]
+    fn test_auto_dialect_detects_injection_missed_by_generic_dialect() {
+        // MySQL does not support nested comments, so `*/` in the user input closes the comment.
+        let query = "SELECT * FROM users WHERE id = 1 /* /* */ OR 1=1 -- */";
+        let userinput = "/* */ OR 1=1 -- ";
+        assert!(!detect_sql_injection_str(query, userinput, dialect("generic")).detected);
+
+        let result = detect_sql_injection_auto_dialect(query, userinput);
+        assert!(result.detected);
+        assert!(matches!(result.reason, DetectionReason::TokensHaveDelta));
+        assert_eq!(result.span, Some(36..52));
+        assert_eq!(result.dialect, Some(SqlDialect::MySql));
+        assert_eq!(
+            result.dialects_evaluated,
+            vec![SqlDialect::Generic, SqlDialect::MySql]
+        );
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "detected": true,
+                "reason": "TokensHaveDelta",
+                "span": { "start": 36, "end": 52 },
+                "dialect": "MySql",
+                "dialects_evaluated": ["Generic", "MySql"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_auto_dialect_skips_dialects_that_fail_to_tokenize() {
+        // The string is not terminated in MySQL (backslash escapes the quote).
+        let result =
+            detect_sql_injection_auto_dialect("SELECT * FROM users WHERE name = 'abc\\'", "abc\\");
+        assert!(!result.detected);
+        assert!(matches!(result.reason, DetectionReason::NoChangesFound));
+        assert_eq!(result.span, Some(34..38));
+        assert_eq!(result.dialect, None);
+        assert_eq!(
+            result.dialects_evaluated,
+            vec![
+                SqlDialect::Generic,
+                SqlDialect::PostgreSql,
+                SqlDialect::MsSql,
+                SqlDialect::SQLite,
+                SqlDialect::Oracle
+            ]
+        );
+
+        let result = detect_sql_injection_auto_dialect(
+            "SELECT * FROM users WHERE name = 'hello world",
+            "hello world",
+        );
+        assert!(!result.detected);
+        assert!(matches!(
+            result.reason,
+            DetectionReason::FailedToTokenizeQuery
+        ));
+        assert!(result.dialects_evaluated.is_empty());
+    }
+
+    #[test]
+    fn test_auto_dialect_without_tokenizing() {
+        let result = detect_sql_injection_auto_dialect("SELECT * FROM users", "admin");
+        assert!(!result.detected);
+        assert!(matches!(
+            result.reason,
+            DetectionReason::UserInputNotInQuery
+        ));
+        assert!(result.dialects_evaluated.is_empty());
+
+        let result = detect_sql_injection_auto_dialect("SELECT * FROM users", "SELECT *");
+        assert!(!result.detected);
+        assert!(matches!(result.reason, DetectionReason::CommonSQLString));
+        assert!(result.dialects_evaluated.is_empty());
+    }
+
+    #[test]
+    fn test_auto_dialect_same_results_as_dialects() {
+        let cases = [
+            ("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", true),
+            ("SELECT * FROM users WHERE id = 'a' -- b'", "a' -- b", true),
+            (
+                "SELECT * FROM users WHERE id = 'hello world'",
+                "hello world",
+                false,
+            ),
+            (
+                "SELECT * FROM users WHERE name = 'O''Brien'",
+                "O''Brien",
+                false,
+            ),
+        ];
+        for (query, userinput, detected) in cases {
+            let result = detect_sql_injection_auto_dialect(query, userinput);
+            assert_eq!(result.detected, detected, "{}", query);
+            if detected {
+                assert_eq!(result.dialect, Some(SqlDialect::Generic));
+                assert_eq!(result.dialects_evaluated, vec![SqlDialect::Generic]);
+            } else {
+                assert_eq!(result.dialects_evaluated, AUTO_DIALECT_CANDIDATES.to_vec());
+            }
+        }
+    }
+
+    fn bulk_insert(rows: usize) -> String {
+        "INSERT INTO logs (id, message) VALUES ".to_string()
+            + &vec!["(1, 'hello; world')"; rows].join(", ")
+    }
+
+    #[test]
+    fn test_max_len_small_query() {
+        let query = "SELECT * FROM users WHERE id = '' OR 1=1 -- '";
+        let result = detect_sql_injection_with_max_len(query, "' OR 1=1 -- ", 0, 1000);
+        assert!(result.detected);
+        assert_eq!(result.span, Some(32..44));
+
+        let result = detect_sql_injection_with_max_len(query, "goodbye", 0, 10);
+        assert!(matches!(
+            result.reason,
+            DetectionReason::UserInputNotInQuery
+        ));
+    }
+
+    #[test]
+    fn test_max_len_only_checks_statements_with_user_input() {
+        let bulk_insert = bulk_insert(1000);
+        let query =
+            bulk_insert.clone() + "; SELECT * FROM users WHERE id = '' OR 1=1 -- '; SELECT 1";
+        let result = detect_sql_injection_with_max_len(&query, "' OR 1=1 -- ", 8, 1000);
+        assert!(result.detected);
+        assert!(matches!(result.reason, DetectionReason::TokensHaveDelta));
+        let span = result.span.unwrap();
+        assert_eq!(&query[span], "' OR 1=1 -- ");
+
+        let query = bulk_insert.clone() + "; SELECT * FROM users WHERE name = 'hello world'";
+        let result = detect_sql_injection_with_max_len(&query, "hello world", 8, 1000);
+        assert!(!result.detected);
+        assert!(matches!(result.reason, DetectionReason::NoChangesFound));
+        assert_eq!(&query[result.span.unwrap()], "hello world");
+
+        let query = bulk_insert.clone() + "; SELECT * FROM users WHERE id = 1; DROP TABLE users";
+        let result = detect_sql_injection_with_max_len(&query, "1; DROP TABLE users", 8, 1000);
+        assert!(result.detected);
+
+        let query = bulk_insert.clone() + "; SELECT * FROM users ORDER BY name asc";
+        let result = detect_sql_injection_with_max_len(&query, "name asc", 8, 1000);
+        assert!(matches!(result.reason, DetectionReason::CommonSQLString));
+        assert_eq!(&query[result.span.unwrap()], "name asc");
+    }
+
+    #[test]
+    fn test_max_len_order_by_list_checks_other_statements() {
+        // Only the first occurrence follows ORDER BY, the second one is an injection.
+        let query = "SELECT * FROM users ORDER BY name, id desc; SELECT name, id desc FROM users";
+        assert!(detect_sql_injection_str(query, "name, id desc", 8).detected);
+        let result = detect_sql_injection_with_max_len(query, "name, id desc", 8, 45);
+        assert!(result.detected);
+        assert_eq!(result.span, Some(51..64));
+
+        let query =
+            "SELECT * FROM users ORDER BY name, id desc; SELECT * FROM logs ORDER BY name, id desc";
+        let result = detect_sql_injection_with_max_len(query, "name, id desc", 8, 45);
+        assert!(matches!(result.reason, DetectionReason::CommonSQLString));
+        assert_eq!(result.span, Some(29..42));
+    }
+
+    #[test]
+    fn test_max_len_dialect_specific_quoting() {
+        // The statement is not split at the semicolon inside of the quoted identifier, string or
+        // comment, which would leave the rest of the statement in an unterminated string.
+        let bulk_insert = bulk_insert(1000);
+        for (statement, dia) in [
+            ("SELECT [a;'] FROM users WHERE id = 1 OR 1=1", "mssql"),
+            (
+                "SELECT q'[it's; ok]' FROM dual WHERE id = 1 OR 1=1",
+                "oracle",
+            ),
+            (
+                "SELECT /* a /* b */ ; ' */ * FROM users WHERE id = 1 OR 1=1",
+                "postgresql",
+            ),
+        ] {
+            let query = bulk_insert.clone() + "; " + statement;
+            let result = detect_sql_injection_with_max_len(&query, "1 OR 1=1", dialect(dia), 1000);
+            assert!(result.detected, "{}", statement);
+            assert_eq!(&query[result.span.unwrap()], "1 OR 1=1");
+        }
+    }
+
+    #[test]
+    fn test_max_len_query_too_large() {
+        let bulk_insert = bulk_insert(1000);
+        let result = detect_sql_injection_with_max_len(&bulk_insert, "hello; world", 8, 1000);
+        assert!(!result.detected);
+        assert!(matches!(result.reason, DetectionReason::QueryTooLarge));
+        assert_eq!(result.span, Some(43..55));
+        assert!(!detect_sql_injection_str(&bulk_insert, "hello; world", 8).detected);
+
+        let query = "INSERT INTO logs (message) VALUES ('hello world'), ('hello world')";
+        let result = detect_sql_injection_with_max_len(query, "hello world", 9, 32);
+        assert!(matches!(result.reason, DetectionReason::QueryTooLarge));
+        assert_eq!(result.span, Some(36..47));
+        let query = query.to_string() + "; SELECT * FROM users WHERE id = '' OR 1=1 -- '";
+        let result = detect_sql_injection_with_max_len(&query, "' OR 1=1 -- ", 9, 64);
+        assert!(result.detected);
+        assert_eq!(result.span, Some(100..112));
+
+        // Another occurrence of the user input is still an injection.
+        let query = bulk_insert.clone() + "; SELECT * FROM users WHERE id = 'hello; world' OR 1=1";
+        let result = detect_sql_injection_with_max_len(&query, "hello; world' OR 1=1", 8, 1000);
+        assert!(result.detected);
+        let query = bulk_insert.clone() + "; SELECT * FROM logs WHERE message = 'hello; world'";
+        let result = detect_sql_injection_with_max_len(&query, "hello; world", 8, 1000);
+        assert!(matches!(result.reason, DetectionReason::QueryTooLarge));
+    }
+
+    #[test]
+    fn test_max_len_same_results_as_detect_sql_injection_str() {
+        let bulk_insert = bulk_insert(100);
+        let cases = [
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "' OR 1=1 -- ",
+            ),
+            ("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1"),
+            (
+                "SELECT * FROM users WHERE name = 'a;b' OR 1=1",
+                "a;b' OR 1=1",
+            ),
+            ("SELECT * FROM users WHERE name = 'a;b'", "a;b"),
+            (
+                "SELECT * FROM users WHERE id = 'hello world'",
+                "hello world",
+            ),
+            (
+                "SELECT * FROM users /* ; */ WHERE id = 1 OR 1=1",
+                "1 OR 1=1",
+            ),
+        ];
+        for (statement, userinput) in cases {
+            for dialect in get_supported_dialects() {
+                let query = format!("{}; {}", bulk_insert, statement);
+                assert_eq!(
+                    detect_sql_injection_with_max_len(&query, userinput, dialect, 200).detected,
+                    detect_sql_injection_str(&query, userinput, dialect).detected,
+                    "{} with user input {} in dialect {}",
+                    statement,
+                    userinput,
+                    dialect
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,150 @@
+use super::detect_sql_injection::{
+    detect_sql_injection_str, replace_user_input_with_safe_str, DetectionReason,
+};
+use super::have_comments_changed::first_changed_comment;
+use super::have_token_categories_changed::first_token_category_difference;
+use super::helpers::find_case_insensitive::CaseInsensitiveStr;
+use super::tokenize_query::tokenize_query;
+use serde::Serialize;
+use sqlparser::tokenizer::{Token, Whitespace};
+use std::ops::Range;
+
+// Amount of tokens to include before and after the first difference.
+const WINDOW_SIZE: usize = 5;
+
+#[derive(Debug, Serialize)]
+pub struct SqlInjectionExplanation {
+    pub detected: bool,
+    pub reason: DetectionReason,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Range<usize>>,
+    // Only set when an injection was detected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_diff: Option<TokenDiff>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenDiff {
+    // Index of the first token in `original_tokens` and `safe_tokens`.
+    pub window_start: usize,
+    pub original_tokens: Vec<String>,
+    pub safe_tokens: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_difference: Option<TokenDifference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_comment: Option<CommentDifference>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenDifference {
+    pub index: usize,
+    // `None` if the query has no token at this index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentDifference {
+    // `None` if the query has no comment at this position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe: Option<String>,
+}
+
+/* Same as detect_sql_injection_str, but when an injection is detected it also returns the evidence
+ * of the detector :
+ * - The tokens of the original query and of the query with the user input replaced by a safe
+ *   string (see replace_user_input_with_safe_str), around the first difference
+ * - The first token that is of a different category in both queries (see
+ *   have_token_categories_changed.rs), or the first comment that was added, removed or altered
+ *   (see have_comments_changed.rs) when the reason is CommentStructureAltered
+ */
+pub fn explain_sql_injection_str(
+    query_raw: &str,
+    userinput_raw: &str,
+    dialect: i32,
+) -> SqlInjectionExplanation {
+    let detection_results = detect_sql_injection_str(query_raw, userinput_raw, dialect);
+    // The detector doesn't keep the tokens it compared, so both queries are tokenized again.
+    let token_diff = detection_results.detected.then(|| {
+        let tokens = tokenize_query(query_raw, dialect);
+        let query_without_input =
+            replace_user_input_with_safe_str(&CaseInsensitiveStr::new(query_raw), userinput_raw);
+        let tokens_without_input = tokenize_query(&query_without_input, dialect);
+        diff_tokens(
+            &detection_results.reason,
+            &tokens,
+            &tokens_without_input,
+            dialect,
+        )
+    });
+
+    SqlInjectionExplanation {
+        detected: detection_results.detected,
+        reason: detection_results.reason,
+        span: detection_results.span,
+        token_diff,
+    }
+}
+
+fn diff_tokens(
+    reason: &DetectionReason,
+    tokens: &[Token],
+    tokens_without_input: &[Token],
+    dialect: i32,
+) -> TokenDiff {
+    // The first comment that changed for CommentStructureAltered, the first token of a different
+    // category otherwise.
+    let first_difference = match reason {
+        DetectionReason::CommentStructureAltered => {
+            first_changed_comment(tokens, tokens_without_input, dialect)
+        }
+        _ => first_token_category_difference(tokens, tokens_without_input),
+    };
+
+    // Center the window around the first difference.
+    let center = first_difference.unwrap_or(0);
+    let window_start = center.saturating_sub(WINDOW_SIZE);
+    let window = |tokens: &[Token]| -> Vec<String> {
+        tokens
+            .iter()
+            .skip(window_start)
+            .take(center - window_start + WINDOW_SIZE + 1)
+            .map(Token::to_string)
+            .collect()
+    };
+
+    let changed_comment = match reason {
+        DetectionReason::CommentStructureAltered => {
+            first_difference.map(|index| CommentDifference {
+                original: comment_at(tokens, index),
+                safe: comment_at(tokens_without_input, index),
+            })
+        }
+        _ => None,
+    };
+
+    TokenDiff {
+        window_start,
+        original_tokens: window(tokens),
+        safe_tokens: window(tokens_without_input),
+        first_difference: first_difference.map(|index| TokenDifference {
+            index,
+            original: tokens.get(index).map(Token::to_string),
+            safe: tokens_without_input.get(index).map(Token::to_string),
+        }),
+        changed_comment,
+    }
+}
+
+fn comment_at(tokens: &[Token], index: usize) -> Option<String> {
+    match tokens.get(index) {
+        Some(Token::Whitespace(
+            comment @ (Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_)),
+        )) => Some(comment.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::detect_sql_injection::DetectionReason;
+    use crate::sql_injection::explain_sql_injection::explain_sql_injection_str;
+    use serde_json::json;
+
+    #[test]
+    fn test_explain_not_an_injection() {
+        let explanation = explain_sql_injection_str(
+            "SELECT * FROM users WHERE id = 'hello world'",
+            "hello world",
+            9,
+        );
+        assert!(explanation.token_diff.is_none());
+        assert_eq!(
+            serde_json::to_value(&explanation).unwrap(),
+            json!({ "detected": false, "reason": "NoChangesFound", "span": { "start": 32, "end": 43 } })
+        );
+    }
+
+    #[test]
+    fn test_explain_tokens_have_delta() {
+        // `1` -> `aaaaaaaa` is not a difference, the first extra token is.
+        let explanation =
+            explain_sql_injection_str("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 9);
+        assert_eq!(
+            serde_json::to_value(&explanation).unwrap(),
+            json!({
+                "detected": true,
+                "reason": "TokensHaveDelta",
+                "span": { "start": 31, "end": 39 },
+                "token_diff": {
+                    "window_start": 10,
+                    "original_tokens": ["id", " ", "=", " ", "1", " ", "OR", " ", "1", "=", "1"],
+                    "safe_tokens": ["id", " ", "=", " ", "aaaaaaaa"],
+                    "first_difference": { "index": 15, "original": " " }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_explain_added_comment() {
+        let explanation = explain_sql_injection_str("SELECT 1 -- a\n-- b", "a\n-- b", 0);
+        assert!(matches!(
+            explanation.reason,
+            DetectionReason::TokensHaveDelta
+        ));
+        let token_diff = explanation.token_diff.unwrap();
+        assert_eq!(token_diff.window_start, 0);
+        assert_eq!(
+            token_diff.original_tokens,
+            vec!["SELECT", " ", "1", " ", "-- a\n", "-- b"]
+        );
+        assert_eq!(
+            token_diff.safe_tokens,
+            vec!["SELECT", " ", "1", " ", "-- aaaaaa"]
+        );
+
+        let first_difference = token_diff.first_difference.unwrap();
+        assert_eq!(first_difference.index, 5);
+        assert_eq!(first_difference.original.as_deref(), Some("-- b"));
+        assert_eq!(first_difference.safe, None);
+        assert!(token_diff.changed_comment.is_none());
+    }
+
+    #[test]
+    fn test_explain_changed_comment() {
+        // The structure of an Oracle optimizer hint changed, the amount of tokens is the same.
+        let explanation = explain_sql_injection_str(
+            "SELECT /*+ INDEX(users a) FULL(users) */ * FROM users",
+            "a) FULL(users",
+            13,
+        );
+        assert!(matches!(
+            explanation.reason,
+            DetectionReason::CommentStructureAltered
+        ));
+        let token_diff = explanation.token_diff.unwrap();
+        assert_eq!(token_diff.window_start, 0);
+        assert_eq!(token_diff.first_difference.unwrap().index, 2);
+
+        let changed_comment = token_diff.changed_comment.unwrap();
+        assert_eq!(
+            changed_comment.original.as_deref(),
+            Some("/*+ INDEX(users a) FULL(users) */")
+        );
+        assert_eq!(
+            changed_comment.safe.as_deref(),
+            Some("/*+ INDEX(users aaaaaaaaaaaaa) */")
+        );
+    }
+
+    #[test]
+    fn test_explain_window_is_limited() {
+        let query = "SELECT a, b, c, d, e, f FROM users WHERE id = 1 OR 1=1 AND g = h AND i = j";
+        let explanation = explain_sql_injection_str(query, "1 OR 1=1", 9);
+        let token_diff = explanation.token_diff.unwrap();
+        let index = token_diff.first_difference.unwrap().index;
+        assert_eq!(token_diff.window_start, index - 5);
+        assert_eq!(token_diff.original_tokens.len(), 11);
+    }
+}
@@ -6,7 +6,7 @@ use sqlparser::tokenizer::{Token, Whitespace};
  *  tokens vector : [Number(..), Eq, Number(..), Token::Whitespace(Space), Token::Whitespace(MultiLineComment(" Multiline Comment "))]
  *  output : [Whitespace(MultiLineComment(" Multiline Comment ")] (Returns whitespace vector)
  */
-pub fn filter_for_comment_tokens(tokens: Vec<Token>) -> Vec<Whitespace> {
+pub fn filter_for_comment_tokens(tokens: &[Token]) -> Vec<Whitespace> {
     let mut comments_vector: Vec<Whitespace> = Vec::new();
     for token in tokens {
         if let Token::Whitespace(whitespace) = token {
@@ -18,7 +18,7 @@ pub fn filter_for_comment_tokens(tokens: Vec<Token>) -> Vec<Whitespace> {
             );
 
             if whitespace_is_comment {
-                comments_vector.push(whitespace);
+                comments_vector.push(whitespace.clone());
             }
         }
     }
@@ -1,4 +1,7 @@
 use super::filter_for_comment_tokens::filter_for_comment_tokens;
+use super::have_token_categories_changed::have_token_categories_changed;
+use super::sql_dialect::SqlDialect;
+use super::tokenize_query::tokenize_query;
 use crate::diff_in_vec_len;
 use sqlparser::tokenizer::{Token, Whitespace};
 
@@ -8,33 +11,63 @@ use sqlparser::tokenizer::{Token, Whitespace};
  * - Makes sure amount of singleline & multiline comments remains the same
  * - Makes sure the prefix and length of comment remains the same for singeline
  * - Makes sure the length of the comment remains the same for multiline
+ * - Makes sure the structure of the content remains the same for MySQL executable comments and
+ *   optimizer hints (and Oracle optimizer hints)
  */
-pub fn have_comments_changed(tokens1: Vec<Token>, tokens2: Vec<Token>) -> bool {
+pub fn have_comments_changed(tokens1: &[Token], tokens2: &[Token], dialect: i32) -> bool {
+    first_changed_comment(tokens1, tokens2, dialect).is_some()
+}
+
+/* Same checks as have_comments_changed, but returns the index of the token of the first comment
+ * that changed (in tokens1, or in tokens2 if tokens1 has less comments).
+ */
+pub fn first_changed_comment(tokens1: &[Token], tokens2: &[Token], dialect: i32) -> Option<usize> {
     // Filter token vectors based on type (singleline and multiline)
     let comment_tokens1: Vec<Whitespace> = filter_for_comment_tokens(tokens1);
     let comment_tokens2: Vec<Whitespace> = filter_for_comment_tokens(tokens2);
 
-    // Do an early return if the lengths don't match -> structure is different.
-    if diff_in_vec_len!(comment_tokens1, comment_tokens2) {
-        return true;
-    }
-
     // Loop over comments :
-    for i in 0..comment_tokens1.len() {
+    let mut changed_comment = None;
+    for i in 0..comment_tokens1.len().min(comment_tokens2.len()) {
         let comment_token1: Whitespace = comment_tokens1[i].clone();
         let comment_token2: Whitespace = comment_tokens2[i].clone();
-        if let Whitespace::SingleLineComment { comment, prefix } = comment_token1 {
-            if comment_token_differs_from_singleline(comment, prefix, comment_token2) {
-                return true;
-            }
+        let differs = if let Whitespace::SingleLineComment { comment, prefix } = comment_token1 {
+            comment_token_differs_from_singleline(comment, prefix, comment_token2)
         } else if let Whitespace::MultiLineComment(comment) = comment_token1 {
-            if comment_token_differs_from_multiline(comment, comment_token2) {
-                return true;
-            }
+            comment_token_differs_from_multiline(comment, comment_token2, dialect)
+        } else {
+            false
+        };
+        if differs {
+            changed_comment = Some(i);
+            break;
         }
     }
 
-    false
+    // If the lengths don't match, the first extra comment changed the structure as well.
+    if changed_comment.is_none() && diff_in_vec_len!(comment_tokens1, comment_tokens2) {
+        changed_comment = Some(comment_tokens1.len().min(comment_tokens2.len()));
+    }
+
+    changed_comment
+        .and_then(|nth| nth_comment_index(tokens1, nth).or(nth_comment_index(tokens2, nth)))
+}
+
+// Returns the index in the token vector of the nth comment.
+fn nth_comment_index(tokens: &[Token], nth: usize) -> Option<usize> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| {
+            matches!(
+                token,
+                Token::Whitespace(
+                    Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_)
+                )
+            )
+        })
+        .nth(nth)
+        .map(|(index, _)| index)
 }
 
 /* Optimalization to keep in mind : We only check length of comments since in case of attack
@@ -65,11 +98,72 @@ fn comment_token_differs_from_singleline(
 /* Optimalization to keep in mind : We only check length of comments since in case of attack
  *      the length of the comment will only be able to increase.
 */
-fn comment_token_differs_from_multiline(comment1: String, comment_token2: Whitespace) -> bool {
+fn comment_token_differs_from_multiline(
+    comment1: String,
+    comment_token2: Whitespace,
+    dialect: i32,
+) -> bool {
     if let Whitespace::MultiLineComment(comment2) = comment_token2 {
+        let prefix1 = executable_comment_prefix(&comment1, dialect);
+        let prefix2 = executable_comment_prefix(&comment2, dialect);
+        if prefix1.is_some() || prefix2.is_some() {
+            // The content of these comments is executed or interpreted by the server, so turning
+            // a regular comment into one of these (or changing the kind) alters the structure.
+            return match (prefix1, prefix2) {
+                (Some(prefix1), Some(prefix2)) if prefix1 == prefix2 => has_content_changed(
+                    &comment1[prefix1.len()..],
+                    &comment2[prefix2.len()..],
+                    dialect,
+                ),
+                _ => true,
+            };
+        }
         // The length of both comments are not the same -> Strucutre is altered.
         return comment2.len().abs_diff(comment1.len()) != 0;
     }
 
     true // So if it's a singleline whitespace for example.
 }
+
+/* MySQL (and MariaDB) executes the content of `/*! ... */` (optionally with a version, e.g.
+ * `/*!50000 ... */`) and interprets `/*+ ... */` as optimizer hints, Oracle only has the hints.
+ * For the other dialects these are regular comments. Returns the marker (and version) that
+ * precedes the content of the comment.
+ */
+fn executable_comment_prefix(comment: &str, dialect: i32) -> Option<&str> {
+    let executable = match SqlDialect::try_from(dialect) {
+        Ok(SqlDialect::MySql | SqlDialect::MariaDb) => {
+            comment.starts_with('!') || comment.starts_with('+')
+        }
+        Ok(SqlDialect::Oracle) => comment.starts_with('+'),
+        _ => false,
+    };
+    if !executable {
+        return None;
+    }
+
+    let version_len = comment[1..]
+        .bytes()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count();
+    let prefix_len = if comment.starts_with('!') {
+        1 + version_len
+    } else {
+        1
+    };
+    Some(&comment[..prefix_len])
+}
+
+/* Tokenizes the content of both executable comments and compares the structure, the same way as
+ * the query itself : the user input can be e.g. an index name in `/*+ INDEX(users idx_name) */`
+ * without altering the hint. Falls back to comparing the content if it can't be tokenized.
+ */
+fn has_content_changed(content1: &str, content2: &str, dialect: i32) -> bool {
+    let tokens1 = tokenize_query(content1, dialect);
+    let tokens2 = tokenize_query(content2, dialect);
+    if tokens1.is_empty() || tokens2.is_empty() {
+        return content1 != content2;
+    }
+
+    diff_in_vec_len!(tokens1, tokens2) || have_token_categories_changed(&tokens1, &tokens2)
+}
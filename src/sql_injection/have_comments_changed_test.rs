@@ -1,21 +1,29 @@
 #[cfg(test)]
 mod tests {
-    use crate::sql_injection::have_comments_changed::have_comments_changed;
+    use crate::sql_injection::have_comments_changed::{
+        first_changed_comment, have_comments_changed,
+    };
     use crate::sql_injection::tokenize_query::tokenize_query;
 
     macro_rules! comments_changed {
         ($query1:expr, $query2:expr) => {
-            let tokens1 = tokenize_query($query1, 0);
-            let tokens2 = tokenize_query($query2, 0);
-            assert!(have_comments_changed(tokens1, tokens2))
+            comments_changed!($query1, $query2, 0)
+        };
+        ($query1:expr, $query2:expr, $dialect:expr) => {
+            let tokens1 = tokenize_query($query1, $dialect);
+            let tokens2 = tokenize_query($query2, $dialect);
+            assert!(have_comments_changed(&tokens1, &tokens2, $dialect))
         };
     }
 
     macro_rules! not_comments_changed {
         ($query1:expr, $query2:expr) => {
-            let tokens1 = tokenize_query($query1, 0);
-            let tokens2 = tokenize_query($query2, 0);
-            assert!(!have_comments_changed(tokens1, tokens2))
+            not_comments_changed!($query1, $query2, 0)
+        };
+        ($query1:expr, $query2:expr, $dialect:expr) => {
+            let tokens1 = tokenize_query($query1, $dialect);
+            let tokens2 = tokenize_query($query2, $dialect);
+            assert!(!have_comments_changed(&tokens1, &tokens2, $dialect))
         };
     }
 
@@ -150,4 +158,84 @@ mod tests {
             FROM --1;"
         );
     }
+
+    #[test]
+    fn test_optimizer_hints_content_compared() {
+        not_comments_changed!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users",
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users WHERE id = 1",
+            8
+        );
+        // The content of the hint is compared by its tokens.
+        not_comments_changed!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users",
+            "SELECT /*+ MAX_EXECUTION_TIME(9999) */ * FROM users",
+            8
+        );
+        comments_changed!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1) BKA(t) */ * FROM users",
+            "SELECT /*+ MAX_EXECUTION_TIME(aaaaaaaaa) */ * FROM users",
+            8
+        );
+        comments_changed!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users",
+            "SELECT /*+ MAX_EXECUTION_TIME(1000); */ * FROM users",
+            8
+        );
+        // A regular comment of the same length :
+        comments_changed!(
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users",
+            "SELECT /*aaaaaaaaaaaaaaaaaaaaaaaaaaa */ * FROM users",
+            8
+        );
+        comments_changed!(
+            "SELECT /*aaaaaaaaaaaaaaaaaaaaaaaaaaa */ * FROM users",
+            "SELECT /*+ MAX_EXECUTION_TIME(1000) */ * FROM users",
+            8
+        );
+    }
+
+    #[test]
+    fn test_executable_comments_content_compared() {
+        let tokens1 = tokenize_query("SELECT * FROM users /*+ INDEX(users a) */", 8);
+        let tokens2 = tokenize_query("SELECT * FROM users /*+ INDEX(users b) */", 8);
+        let tokens3 = tokenize_query("SELECT * FROM users /*aaaaaaaaaaaaaaaaaa */", 8);
+        let tokens4 = tokenize_query("SELECT * FROM users /*+ INDEX(users a, b) */", 8);
+        assert!(!have_comments_changed(&tokens1, &tokens1, 8));
+        assert!(!have_comments_changed(&tokens1, &tokens2, 8));
+        assert!(have_comments_changed(&tokens1, &tokens3, 8));
+        assert!(have_comments_changed(&tokens1, &tokens4, 8));
+
+        // Oracle only has optimizer hints.
+        let tokens1 = tokenize_query("SELECT * FROM users /*+ INDEX(users a) */", 13);
+        let tokens2 = tokenize_query("SELECT * FROM users /*+ INDEX(users a, b) */", 13);
+        assert!(have_comments_changed(&tokens1, &tokens2, 13));
+        let tokens1 = tokenize_query("SELECT * FROM users /*! WHERE 1=1 */", 13);
+        let tokens2 = tokenize_query("SELECT * FROM users /*! WHERE 2=2 */", 13);
+        assert!(!have_comments_changed(&tokens1, &tokens2, 13));
+
+        // For other dialects these are regular comments.
+        let tokens1 = tokenize_query("SELECT * FROM users /*! WHERE 1=1 */", 12);
+        let tokens2 = tokenize_query("SELECT * FROM users /*! WHERE 2=2 */", 12);
+        assert!(!have_comments_changed(&tokens1, &tokens2, 12));
+        let tokens1 = tokenize_query("SELECT * FROM users /*+ INDEX(users a) */", 9);
+        let tokens2 = tokenize_query("SELECT * FROM users /*+ INDEX(users b) */", 9);
+        assert!(!have_comments_changed(&tokens1, &tokens2, 9));
+
+        // Regular comments of the same length are still not compared by content.
+        not_comments_changed!("SELECT /* Hello */ 1", "SELECT /* World */ 1");
+    }
+
+    #[test]
+    fn test_first_changed_comment() {
+        let tokens1 = tokenize_query("SELECT /*1*/ 1 -- abc", 0);
+        let tokens2 = tokenize_query("SELECT /*1*/ 1 -- abcd", 0);
+        assert_eq!(first_changed_comment(&tokens1, &tokens1, 0), None);
+        assert_eq!(first_changed_comment(&tokens1, &tokens2, 0), Some(6));
+
+        // The first extra comment, in the tokens that have it.
+        let tokens2 = tokenize_query("SELECT /*1*/ 1", 0);
+        assert_eq!(first_changed_comment(&tokens1, &tokens2, 0), Some(6));
+        assert_eq!(first_changed_comment(&tokens2, &tokens1, 0), Some(6));
+    }
 }
@@ -0,0 +1,85 @@
+use sqlparser::tokenizer::{Token, Whitespace};
+use std::mem::{discriminant, Discriminant};
+
+// The category of a token, see have_token_categories_changed.
+#[derive(PartialEq)]
+enum TokenCategory {
+    // Keywords, identifiers, numbers, strings and placeholders
+    Operand,
+    SemiColon,
+    // Parentheses, brackets, commas and periods
+    Punctuation,
+    Whitespace(Discriminant<Whitespace>),
+    // Operators (e.g. `=`, `||`, `::`) and all other tokens
+    Operator,
+}
+
+/* Compares the category of the tokens position-by-position, e.g. a keyword in one query that is a
+ * semicolon in the other query. This is coarser than comparing the exact kind of the tokens :
+ * replacing the user input with a safe string turns the keyword or literal it covers into an
+ * identifier (e.g. `INSERT` or `'John'` -> `aaaaaa`) without altering the structure of the query.
+ */
+pub fn have_token_categories_changed(tokens1: &[Token], tokens2: &[Token]) -> bool {
+    first_token_category_difference(tokens1, tokens2).is_some()
+}
+
+/* Returns the index of the first token that is of a different category in both token vectors.
+ * If one vector is a prefix of the other, the index of the first extra token is returned.
+ * `tokens1` are the tokens of the original query : an operator in the original query that is an
+ * operand in the query without user input is not a difference. The operator was the user input
+ * itself, replaced as a whole (e.g. `>=` picked from a filter UI : `WHERE age >= 18` becomes
+ * `WHERE age aa 18`), which doesn't alter the rest of the query.
+ */
+pub fn first_token_category_difference(tokens1: &[Token], tokens2: &[Token]) -> Option<usize> {
+    let difference = tokens1.iter().zip(tokens2).position(|(token1, token2)| {
+        match (token_category(token1), token_category(token2)) {
+            (TokenCategory::Operator, TokenCategory::Operand) => false,
+            (category1, category2) => category1 != category2,
+        }
+    });
+
+    match difference {
+        Some(index) => Some(index),
+        None if tokens1.len() != tokens2.len() => Some(tokens1.len().min(tokens2.len())),
+        None => None,
+    }
+}
+
+fn token_category(token: &Token) -> TokenCategory {
+    match token {
+        Token::Word(_)
+        | Token::Number(_, _)
+        | Token::Char(_)
+        | Token::SingleQuotedString(_)
+        | Token::DoubleQuotedString(_)
+        | Token::TripleSingleQuotedString(_)
+        | Token::TripleDoubleQuotedString(_)
+        | Token::DollarQuotedString(_)
+        | Token::SingleQuotedByteStringLiteral(_)
+        | Token::DoubleQuotedByteStringLiteral(_)
+        | Token::TripleSingleQuotedByteStringLiteral(_)
+        | Token::TripleDoubleQuotedByteStringLiteral(_)
+        | Token::SingleQuotedRawStringLiteral(_)
+        | Token::DoubleQuotedRawStringLiteral(_)
+        | Token::TripleSingleQuotedRawStringLiteral(_)
+        | Token::TripleDoubleQuotedRawStringLiteral(_)
+        | Token::NationalStringLiteral(_)
+        | Token::QuoteDelimitedStringLiteral(_)
+        | Token::NationalQuoteDelimitedStringLiteral(_)
+        | Token::EscapedStringLiteral(_)
+        | Token::UnicodeStringLiteral(_)
+        | Token::HexStringLiteral(_)
+        | Token::Placeholder(_) => TokenCategory::Operand,
+        Token::SemiColon => TokenCategory::SemiColon,
+        Token::LParen
+        | Token::RParen
+        | Token::LBracket
+        | Token::RBracket
+        | Token::LBrace
+        | Token::RBrace
+        | Token::Comma
+        | Token::Period => TokenCategory::Punctuation,
+        Token::Whitespace(whitespace) => TokenCategory::Whitespace(discriminant(whitespace)),
+        _ => TokenCategory::Operator,
+    }
+}
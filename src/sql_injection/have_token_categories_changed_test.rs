@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::have_token_categories_changed::{
+        first_token_category_difference, have_token_categories_changed,
+    };
+    use crate::sql_injection::tokenize_query::tokenize_query;
+
+    fn categories_changed(query1: &str, query2: &str) -> bool {
+        have_token_categories_changed(&tokenize_query(query1, 0), &tokenize_query(query2, 0))
+    }
+
+    #[test]
+    fn test_same_token_categories() {
+        assert!(!categories_changed(
+            "INSERT INTO users VALUES ('John', 1)",
+            "aaaaaa INTO users VALUES (aaaaaa, a)"
+        ));
+        assert!(!categories_changed(
+            "SELECT * FROM users WHERE id = $1",
+            "SELECT * FROM users WHERE id = aa"
+        ));
+    }
+
+    #[test]
+    fn test_different_token_categories() {
+        // Same amount of tokens, but a semicolon instead of a space.
+        assert!(categories_changed("SELECT x;SELECT y", "SELECT x SELECT y"));
+        assert!(categories_changed("SELECT a a b", "SELECT a = b"));
+        assert!(categories_changed("SELECT (a)", "SELECT +a+"));
+        assert!(categories_changed("SELECT 1", "SELECT 1; DROP TABLE users"));
+    }
+
+    #[test]
+    fn test_operator_replaced_by_operand() {
+        // An operator in the user input (e.g. `>=` instead of `=`) becomes an identifier.
+        assert!(!categories_changed(
+            "SELECT * FROM users WHERE age >= 18",
+            "SELECT * FROM users WHERE age aa 18"
+        ));
+        // The other way around, an operand of the original query became an operator.
+        assert!(categories_changed(
+            "SELECT * FROM users WHERE age aa 18",
+            "SELECT * FROM users WHERE age >= 18"
+        ));
+        assert!(!categories_changed(
+            "SELECT * FROM users WHERE name LIKE 'a%'",
+            "SELECT * FROM users WHERE name aaaa 'a%'"
+        ));
+    }
+
+    fn first_difference(query1: &str, query2: &str) -> Option<usize> {
+        first_token_category_difference(&tokenize_query(query1, 0), &tokenize_query(query2, 0))
+    }
+
+    #[test]
+    fn test_first_token_category_difference() {
+        // `SELECT`, ` `, `x`, `;` vs `SELECT`, ` `, `x`, ` `
+        assert_eq!(
+            first_difference("SELECT x;SELECT y", "SELECT x SELECT y"),
+            Some(3)
+        );
+        assert_eq!(
+            first_difference("SELECT 1 -- abc", "SELECT 1 /* a */"),
+            Some(4)
+        );
+        // A literal that became an identifier is not a difference.
+        assert_eq!(
+            first_difference(
+                "SELECT * FROM users WHERE id = 'abc'",
+                "SELECT * FROM users WHERE id = aaaaa"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_first_token_category_difference_lengths() {
+        assert_eq!(
+            first_difference("SELECT 1", "SELECT 1; DROP TABLE users"),
+            Some(3)
+        );
+        assert_eq!(
+            first_difference("SELECT 1; DROP TABLE users", "SELECT 1"),
+            Some(3)
+        );
+        assert_eq!(first_difference("", "SELECT 1"), Some(0));
+    }
+}
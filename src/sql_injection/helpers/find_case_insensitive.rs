@@ -0,0 +1,155 @@
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::CharIndices;
+
+/* Finds all non-overlapping occurrences of `needle` in `haystack`, ignoring case.
+ * The returned ranges are byte offsets in the original `haystack`, lowercasing can change the
+ * length of a character (e.g. `İ` becomes `i̇`), so the offsets of the lowercased string can't be used.
+ */
+pub fn find_case_insensitive(haystack: &str, needle: &str) -> Vec<Range<usize>> {
+    CaseInsensitiveStr::new(haystack).find_all(needle)
+}
+
+/* A string that is lowercased once, so that it can be searched (ignoring case) for multiple
+ * needles, e.g. a query that is checked for every user input of a request.
+ */
+pub struct CaseInsensitiveStr<'a> {
+    original: &'a str,
+    lowercased: String,
+}
+
+impl<'a> CaseInsensitiveStr<'a> {
+    pub fn new(original: &'a str) -> Self {
+        CaseInsensitiveStr {
+            original,
+            lowercased: original.to_lowercase(),
+        }
+    }
+
+    // For a string that was lowercased before, e.g. a query cached by SqlInjectionDetector.
+    pub fn with_lowercased(original: &'a str, lowercased: String) -> Self {
+        CaseInsensitiveStr {
+            original,
+            lowercased,
+        }
+    }
+
+    pub fn into_lowercased(self) -> String {
+        self.lowercased
+    }
+
+    pub fn original(&self) -> &'a str {
+        self.original
+    }
+
+    pub fn lowercased(&self) -> &str {
+        &self.lowercased
+    }
+
+    pub fn find_all(&self, needle: &str) -> Vec<Range<usize>> {
+        let needle = needle.to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let mut original_offsets = OriginalOffsets::new(self.original);
+        self.lowercased
+            .match_indices(&needle)
+            .map(|(start, matched)| {
+                original_offsets.get(start)..original_offsets.get(start + matched.len())
+            })
+            .collect()
+    }
+
+    pub fn find_first(&self, needle: &str) -> Option<Range<usize>> {
+        let needle = needle.to_lowercase();
+        if needle.is_empty() {
+            return None;
+        }
+
+        let start = self.lowercased.find(&needle)?;
+        let mut original_offsets = OriginalOffsets::new(self.original);
+        Some(original_offsets.get(start)..original_offsets.get(start + needle.len()))
+    }
+}
+
+/* Converts (ascending) byte offsets of the lowercased string into byte offsets of the original
+ * string, by walking over the original characters once. The offsets are the same for ASCII.
+ */
+struct OriginalOffsets<'a> {
+    chars: Option<Peekable<CharIndices<'a>>>,
+    lowercased_offset: usize,
+    len: usize,
+}
+
+impl<'a> OriginalOffsets<'a> {
+    fn new(original: &'a str) -> Self {
+        OriginalOffsets {
+            chars: (!original.is_ascii()).then(|| original.char_indices().peekable()),
+            lowercased_offset: 0,
+            len: original.len(),
+        }
+    }
+
+    // The offset of the original character that the byte at `lowercased_offset` belongs to.
+    fn get(&mut self, lowercased_offset: usize) -> usize {
+        let Some(chars) = self.chars.as_mut() else {
+            return lowercased_offset;
+        };
+
+        while let Some(&(offset, c)) = chars.peek() {
+            let lowercased_len: usize = c.to_lowercase().map(char::len_utf8).sum();
+            if self.lowercased_offset + lowercased_len > lowercased_offset {
+                return offset;
+            }
+            self.lowercased_offset += lowercased_len;
+            chars.next();
+        }
+
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::helpers::find_case_insensitive::{
+        find_case_insensitive, CaseInsensitiveStr,
+    };
+
+    #[test]
+    fn test_find_case_insensitive() {
+        assert_eq!(
+            find_case_insensitive("SELECT * FROM users", "from"),
+            vec![9..13]
+        );
+        assert_eq!(
+            find_case_insensitive("a = 'Abc' OR b = 'aBC'", "abc"),
+            vec![5..8, 18..21]
+        );
+        assert_eq!(find_case_insensitive("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(find_case_insensitive("SELECT 1", "2").is_empty());
+        assert!(find_case_insensitive("SELECT 1", "").is_empty());
+    }
+
+    #[test]
+    fn test_find_case_insensitive_multibyte() {
+        // `İ` is 2 bytes, but lowercases to `i̇` which is 3 bytes.
+        assert_eq!(find_case_insensitive("İ = 'John'", "john"), vec![6..10]);
+        assert_eq!(find_case_insensitive("'ÉCOLE'", "école"), vec![1..7]);
+        assert_eq!(
+            find_case_insensitive("İ 'a' İ 'A'", "'a'"),
+            vec![3..6, 10..13]
+        );
+    }
+
+    #[test]
+    fn test_case_insensitive_str() {
+        let haystack = CaseInsensitiveStr::new("SELECT 'İ', 'i̇' FROM users");
+        assert_eq!(haystack.lowercased(), "select 'i̇', 'i̇' from users");
+        assert_eq!(haystack.find_first("'i̇'"), Some(7..11));
+        assert_eq!(haystack.find_all("'i̇'"), vec![7..11, 13..18]);
+        assert_eq!(haystack.find_first("users"), Some(24..29));
+        assert_eq!(haystack.find_first("posts"), None);
+        assert_eq!(haystack.find_first(""), None);
+    }
+}
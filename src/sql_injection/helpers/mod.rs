@@ -1 +1,4 @@
+pub mod find_case_insensitive;
+pub mod select_decodings_based_on_bitmask;
 pub mod select_dialect_based_on_enum;
+pub mod split_statements;
@@ -0,0 +1,46 @@
+use crate::sql_injection::user_input_decoding::UserInputDecoding;
+
+/*
+Every bit enables a decoding of the user input, they are tried in this order :
+1 -> URL decode
+2 -> HTML entity decode
+4 -> JSON unescape
+8 -> Backslash escape quotes
+e.g. 3 -> URL decode and HTML entity decode, 0 -> no decodings
+*/
+pub fn select_decodings_based_on_bitmask(bitmask: i32) -> Vec<UserInputDecoding> {
+    [
+        (1, UserInputDecoding::UrlDecode),
+        (2, UserInputDecoding::HtmlEntityDecode),
+        (4, UserInputDecoding::JsonUnescape),
+        (8, UserInputDecoding::BackslashEscapeQuotes),
+    ]
+    .into_iter()
+    .filter(|(bit, _)| bitmask & bit != 0)
+    .map(|(_, decoding)| decoding)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::helpers::select_decodings_based_on_bitmask::select_decodings_based_on_bitmask;
+    use crate::sql_injection::user_input_decoding::UserInputDecoding;
+
+    #[test]
+    fn test_select_decodings_based_on_bitmask() {
+        assert!(select_decodings_based_on_bitmask(0).is_empty());
+        assert_eq!(
+            select_decodings_based_on_bitmask(1),
+            vec![UserInputDecoding::UrlDecode]
+        );
+        assert_eq!(
+            select_decodings_based_on_bitmask(6),
+            vec![
+                UserInputDecoding::HtmlEntityDecode,
+                UserInputDecoding::JsonUnescape
+            ]
+        );
+        assert_eq!(select_decodings_based_on_bitmask(15).len(), 4);
+        assert_eq!(select_decodings_based_on_bitmask(16), vec![]);
+    }
+}
@@ -1,4 +1,5 @@
-use sqlparser::dialect::*;
+use crate::sql_injection::sql_dialect::SqlDialect;
+use sqlparser::dialect::Dialect;
 
 /*
 0 -> generic.rs
@@ -14,25 +15,15 @@ use sqlparser::dialect::*;
 10 -> redshift.rs
 11 -> snowflake.rs
 12 -> sqlite.rs
+13 -> oracle.rs
+14 -> mysql.rs (MariaDB)
 */
 pub fn select_dialect_based_on_enum(enumerator: i32) -> Box<dyn Dialect> {
-    // 0 is generic type.
-    match enumerator {
-        0 => Box::new(GenericDialect {}),
-        1 => Box::new(AnsiDialect {}),
-        2 => Box::new(BigQueryDialect {}),
-        3 => Box::new(ClickHouseDialect {}),
-        4 => Box::new(DatabricksDialect {}),
-        5 => Box::new(DuckDbDialect {}),
-        6 => Box::new(HiveDialect {}),
-        7 => Box::new(MsSqlDialect {}),
-        8 => Box::new(MySqlDialect {}),
-        9 => Box::new(PostgreSqlDialect {}),
-        10 => Box::new(RedshiftSqlDialect {}),
-        11 => Box::new(SnowflakeDialect {}),
-        12 => Box::new(SQLiteDialect {}),
-        _ => Box::new(GenericDialect {}),
-    }
+    // 0 is generic type, unknown values also fall back to it. Callers that get the dialect from
+    // outside of the crate have to check it with SqlDialect::try_from first (the bindings do).
+    SqlDialect::try_from(enumerator)
+        .unwrap_or(SqlDialect::Generic)
+        .to_dialect()
 }
 
 #[cfg(test)]
@@ -40,7 +31,7 @@ mod tests {
     use crate::sql_injection::helpers::select_dialect_based_on_enum::select_dialect_based_on_enum;
     use sqlparser::dialect::{
         AnsiDialect, BigQueryDialect, ClickHouseDialect, DatabricksDialect, DuckDbDialect,
-        GenericDialect, HiveDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+        GenericDialect, HiveDialect, MsSqlDialect, MySqlDialect, OracleDialect, PostgreSqlDialect,
         RedshiftSqlDialect, SQLiteDialect, SnowflakeDialect,
     };
 
@@ -59,6 +50,8 @@ mod tests {
         assert!(select_dialect_based_on_enum(10).is::<RedshiftSqlDialect>());
         assert!(select_dialect_based_on_enum(11).is::<SnowflakeDialect>());
         assert!(select_dialect_based_on_enum(12).is::<SQLiteDialect>());
-        assert!(select_dialect_based_on_enum(13).is::<GenericDialect>());
+        assert!(select_dialect_based_on_enum(13).is::<OracleDialect>());
+        assert!(select_dialect_based_on_enum(14).is::<MySqlDialect>());
+        assert!(select_dialect_based_on_enum(15).is::<GenericDialect>());
     }
 }
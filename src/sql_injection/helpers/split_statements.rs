@@ -0,0 +1,297 @@
+use crate::sql_injection::sql_dialect::SqlDialect;
+use std::ops::Range;
+
+/* Splits the query into statements at the semicolons that are not inside of a string, quoted
+ * identifier or comment, the returned ranges are byte offsets in the query (without the
+ * semicolons). This only knows the common quoting and comment syntax : '...', "...", `...`,
+ * -- and /* */ and, depending on the dialect, [...] identifiers (MSSQL and SQLite), nested
+ * /* */ comments (e.g. PostgreSQL), q'[...]' strings (Oracle), # comments (MySQL), backslash
+ * escapes (e.g. MySQL) and dollar-quoted strings (PostgreSQL). Dialects with other quoting (the
+ * triple-quoted and raw strings of BigQuery) are not split, the query is returned as a single
+ * statement. It's a lot cheaper than tokenizing the query, so it can be used on huge queries.
+ */
+pub fn split_statements(query: &str, dialect: i32) -> Vec<Range<usize>> {
+    let dialect = SqlDialect::try_from(dialect).unwrap_or(SqlDialect::Generic);
+    let is_mysql = matches!(dialect, SqlDialect::MySql | SqlDialect::MariaDb);
+    let has_dollar_quotes = matches!(dialect, SqlDialect::PostgreSql | SqlDialect::Generic);
+    let dialect = dialect.to_dialect();
+    if dialect.supports_triple_quoted_string() {
+        return std::iter::once(0..query.len()).collect();
+    }
+    let has_backslash_escapes = dialect.supports_string_literal_backslash_escape();
+    let has_bracket_identifiers = dialect.is_delimited_identifier_start('[');
+    let has_nested_comments = dialect.supports_nested_comments();
+    let has_q_quotes = dialect.supports_quote_delimited_string();
+
+    let bytes = query.as_bytes();
+    let mut statements = Vec::new();
+    let mut statement_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if has_backslash_escapes && bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'[' if has_bracket_identifiers => {
+                i += 1;
+                // `]]` is an escaped bracket.
+                while i < bytes.len() && (bytes[i] != b']' || bytes.get(i + 1) == Some(&b']')) {
+                    if bytes[i] == b']' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'q' | b'Q' if has_q_quotes && is_q_quote_start(bytes, i) => {
+                i = q_quote_end(query, i + 2);
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find_from(query, i, "\n").unwrap_or(bytes.len());
+            }
+            b'#' if is_mysql => {
+                i = find_from(query, i, "\n").unwrap_or(bytes.len());
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = block_comment_end(bytes, i + 2, has_nested_comments);
+            }
+            b'$' if has_dollar_quotes => {
+                if let Some(tag_len) = dollar_quote_tag_len(&bytes[i..]) {
+                    let tag = &query[i..i + tag_len];
+                    i = find_from(query, i + tag_len, tag)
+                        .map_or(bytes.len(), |end| end + tag_len - 1);
+                }
+            }
+            b';' => {
+                statements.push(statement_start..i);
+                statement_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    statements.push(statement_start..bytes.len());
+    statements
+}
+
+// Returns the offset of the `/` that closes the comment, comments can be nested in some dialects.
+fn block_comment_end(bytes: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 1;
+    let mut i = start;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'*', b'/') => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+                i += 1;
+            }
+            (b'/', b'*') if nested => {
+                depth += 1;
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+// Whether the `q` at index i starts a string like `q'[...]'` or `nq'[...]'` (not e.g. `seq'`).
+fn is_q_quote_start(bytes: &[u8], i: usize) -> bool {
+    let is_word_char = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+    let prefix_start = match i.checked_sub(1).map(|j| bytes[j]) {
+        Some(b'n' | b'N') => i - 1,
+        _ => i,
+    };
+
+    bytes.get(i + 1) == Some(&b'\'')
+        && !prefix_start
+            .checked_sub(1)
+            .is_some_and(|j| is_word_char(&bytes[j]))
+}
+
+/* Returns the offset of the `'` that closes a string like `q'[...]'`, where `start` is the offset
+ * of the delimiter. `[`, `{`, `<` and `(` are closed by their counterpart, other delimiters by
+ * the same character.
+ */
+fn q_quote_end(query: &str, start: usize) -> usize {
+    let Some(delimiter) = query.get(start..).and_then(|rest| rest.chars().next()) else {
+        return query.len();
+    };
+    let closing = match delimiter {
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        '(' => ')',
+        c => c,
+    };
+
+    find_from(query, start + delimiter.len_utf8(), &format!("{closing}'"))
+        .map_or(query.len(), |end| end + closing.len_utf8())
+}
+
+fn find_from(query: &str, start: usize, needle: &str) -> Option<usize> {
+    query
+        .get(start..)
+        .and_then(|rest| rest.find(needle))
+        .map(|offset| start + offset)
+}
+
+// The length of the tag if the bytes start with e.g. `$$` or `$tag$` (but not `$1`).
+fn dollar_quote_tag_len(bytes: &[u8]) -> Option<usize> {
+    let name_len = bytes[1..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+        .count();
+    let starts_with_digit = bytes.get(1).is_some_and(|b| b.is_ascii_digit());
+
+    if !starts_with_digit && bytes.get(name_len + 1) == Some(&b'$') {
+        Some(name_len + 2)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::helpers::split_statements::split_statements;
+
+    fn statements(query: &str, dialect: i32) -> Vec<&str> {
+        split_statements(query, dialect)
+            .into_iter()
+            .map(|range| &query[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(statements("SELECT 1", 0), vec!["SELECT 1"]);
+        assert_eq!(
+            statements("SELECT 1; SELECT 2;", 0),
+            vec!["SELECT 1", " SELECT 2", ""]
+        );
+        assert_eq!(statements("", 0), vec![""]);
+    }
+
+    #[test]
+    fn test_semicolons_in_strings_and_comments() {
+        assert_eq!(
+            statements("SELECT ';', \";\", `;` -- ;\n; SELECT 2", 8),
+            vec!["SELECT ';', \";\", `;` -- ;\n", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT 'a'';' /* ; */; SELECT 2", 0),
+            vec!["SELECT 'a'';' /* ; */", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT 1 # ;\n; SELECT 2", 8),
+            vec!["SELECT 1 # ;\n", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT 1 # ;\n; SELECT 2", 9),
+            vec!["SELECT 1 # ", "\n", " SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_backslash_escapes() {
+        assert_eq!(
+            statements("SELECT 'a\\';'; SELECT 2", 8),
+            vec!["SELECT 'a\\';'", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT 'a\\'; SELECT 2", 9),
+            vec!["SELECT 'a\\'", " SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_dollar_quoted_strings() {
+        assert_eq!(
+            statements("SELECT $$;$$; SELECT $tag$;$$;$tag$; SELECT $1;", 9),
+            vec!["SELECT $$;$$", " SELECT $tag$;$$;$tag$", " SELECT $1", ""]
+        );
+        assert_eq!(statements("SELECT $$;", 9), vec!["SELECT $$;"]);
+    }
+
+    #[test]
+    fn test_bracket_identifiers() {
+        assert_eq!(
+            statements("SELECT [a;b], [c]]d;] FROM t; SELECT 2", 7),
+            vec!["SELECT [a;b], [c]]d;] FROM t", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT [a;b] FROM t", 12),
+            vec!["SELECT [a;b] FROM t"]
+        );
+        // Arrays in PostgreSQL.
+        assert_eq!(
+            statements("SELECT a[1]; SELECT ';'", 9),
+            vec!["SELECT a[1]", " SELECT ';'"]
+        );
+    }
+
+    #[test]
+    fn test_quote_delimited_strings() {
+        assert_eq!(
+            statements("SELECT q'[it's; ok]'; SELECT 2", 13),
+            vec!["SELECT q'[it's; ok]'", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT Q'{a;}', nq'!b;!'; SELECT 2", 13),
+            vec!["SELECT Q'{a;}', nq'!b;!'", " SELECT 2"]
+        );
+        // Not a q-quote, `seq` is a word.
+        assert_eq!(
+            statements("SELECT seq'a;'; SELECT 2", 13),
+            vec!["SELECT seq'a;'", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT q'[it's; ok]'; SELECT 2", 9),
+            vec!["SELECT q'[it's", " ok]'; SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_nested_comments() {
+        assert_eq!(
+            statements("SELECT /* a /* b */ ; */ 1; SELECT 2", 9),
+            vec!["SELECT /* a /* b */ ; */ 1", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT /* a /* b */ ; */ 1; SELECT 2", 8),
+            vec!["SELECT /* a /* b */ ", " */ 1", " SELECT 2"]
+        );
+        assert_eq!(
+            statements("SELECT 1 /* /* */ ;", 9),
+            vec!["SELECT 1 /* /* */ ;"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_quoting_is_not_split() {
+        assert_eq!(
+            statements("SELECT \"\"\"a\";\"\"\"; SELECT 2", 2),
+            vec!["SELECT \"\"\"a\";\"\"\"; SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_unterminated() {
+        assert_eq!(statements("SELECT ';", 0), vec!["SELECT ';"]);
+        assert_eq!(statements("SELECT 1 /* ;", 0), vec!["SELECT 1 /* ;"]);
+        assert_eq!(
+            statements("SELECT 'é;'; SELECT 2", 0),
+            vec!["SELECT 'é;'", " SELECT 2"]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::custom_exemptions::{find_custom_exemption, SqlExemption};
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -40,28 +41,37 @@ macro_rules! regex {
     }};
 }
 
-pub fn is_common_sql_string(user_input: &str) -> bool {
-    let is_common_sql_string = COMMON_SQL_STRINGS.contains(&user_input);
+/* Returns the exemption that matched the user input if it's a common SQL string that should not be
+ * flagged, either one of the built-in exemptions or one of the custom exemptions (see
+ * custom_exemptions.rs).
+ */
+pub fn find_exemption(user_input: &str) -> Option<SqlExemption> {
+    find_built_in_exemption(user_input).or_else(|| find_custom_exemption(user_input))
+}
 
-    if is_common_sql_string {
-        return true;
+fn find_built_in_exemption(user_input: &str) -> Option<SqlExemption> {
+    if let Some(common_sql_string) = COMMON_SQL_STRINGS.iter().find(|s| **s == user_input) {
+        return Some(SqlExemption::built_in(common_sql_string));
     }
 
-    if user_input.len() <= 5 && regex!(r"^[0-9a-z]+ [0-9a-z]+$").is_match(user_input) {
+    let re = regex!(r"^[0-9a-z]+ [0-9a-z]+$");
+    if user_input.len() <= 5 && re.is_match(user_input) {
         // It's very difficult to exploit a query using a short string of only alphanumerical chars and a space.
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // Allow short strings with letters, digits and spaces
     // e.g. `select * from "table" where "id" = $1 limit $2`
     //                                           ^^^ `1 l`
-    if user_input.len() <= 3 && regex!(r"^[ 0-9a-z]+$").is_match(user_input) {
-        return true;
+    let re = regex!(r"^[ 0-9a-z]+$");
+    if user_input.len() <= 3 && re.is_match(user_input) {
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
-    if user_input.ends_with(")") && regex!(r"^[0-9]+\)+$").is_match(user_input) {
+    let re = regex!(r"^[0-9]+\)+$");
+    if user_input.ends_with(")") && re.is_match(user_input) {
         // e.g. `2)` or `12345)`
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // e.g. SELECT * FROM users WHERE users.active= 1
@@ -69,27 +79,29 @@ pub fn is_common_sql_string(user_input: &str) -> bool {
     // SELECT * FROM users WHERE users.activaa 1
     // The structure of the query will not be the same
     // it's very difficult to exploit a query using just "e=" as the user input.
-    if user_input.len() == 2
-        && user_input.ends_with("=")
-        && regex!(r"^[a-z]=$").is_match(user_input)
-    {
+    let re = regex!(r"^[a-z]=$");
+    if user_input.len() == 2 && user_input.ends_with("=") && re.is_match(user_input) {
         // If the user input is just a single letter followed by an equal sign, it's not an injection.
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // `:p` from `:param`
     // `1)` from `(1)`
     // `(s` from `(select`
     // `+1` from `version+1`
-    if user_input.len() == 2 && regex!(r"^[a-z0-9:()+]+$").is_match(user_input) {
-        return true;
+    let re = regex!(r"^[a-z0-9:()+]+$");
+    if user_input.len() == 2 && re.is_match(user_input) {
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     if user_input.contains("asc") || user_input.contains("desc") {
         // Check if the user input is a common SQL pattern like "column_name ASC"
         // e.g. https://ghost.org/docs/content-api/#order (Ghost validates the order parameter)
         // SQL identifiers can't start with a number
-        return regex!(r"^[a-z_][a-z0-9_]* +(asc|desc)$").is_match(user_input);
+        let re = regex!(r"^[a-z_][a-z0-9_]* +(asc|desc)$");
+        return re
+            .is_match(user_input)
+            .then(|| SqlExemption::built_in(re.as_str()));
     }
 
     // For the following exemptions there is a consideration to be made that allowing this user input
@@ -99,45 +111,50 @@ pub fn is_common_sql_string(user_input: &str) -> bool {
     // example: SELECT * FROM users WHERE user_id = '${user id}' AND name = '${user_name}'
 
     // e.g. 'a or '1 or 'product-id-123
+    let re = regex!(r"^'[a-z0-9-]+$");
     if user_input.starts_with("'")
         && user_input.len() <= 200
         && !user_input.contains("--")
-        && regex!(r"^'[a-z0-9-]+$").is_match(user_input)
+        && re.is_match(user_input)
     {
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // e.g. a' or 1' or product-id-123'
+    let re = regex!(r"^[a-z0-9-]+'$");
     if user_input.ends_with("'")
         && user_input.len() <= 200
         && !user_input.contains("--")
-        && regex!(r"^[a-z0-9-]+'$").is_match(user_input)
+        && re.is_match(user_input)
     {
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // e.g. "a or "1 or "product-id-123
+    let re = regex!(r#"^"[a-z0-9-]+$"#);
     if user_input.starts_with("\"")
         && user_input.len() <= 200
         && !user_input.contains("--")
-        && regex!(r#"^"[a-z0-9-]+$"#).is_match(user_input)
+        && re.is_match(user_input)
     {
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // e.g. a" or 1" or product-id-123"
+    let re = regex!(r#"^[a-z0-9-]+"$"#);
     if user_input.ends_with("\"")
         && user_input.len() <= 200
         && !user_input.contains("--")
-        && regex!(r#"^[a-z0-9-]+"$"#).is_match(user_input)
+        && re.is_match(user_input)
     {
-        return true;
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     if user_input.contains(".") {
         // Check if it is just a decimal (e.g. `16.2`)
-        if regex!(r"^-?[0-9]+\.[0-9]+$").is_match(user_input) {
-            return true;
+        let re = regex!(r"^-?[0-9]+\.[0-9]+$");
+        if re.is_match(user_input) {
+            return Some(SqlExemption::built_in(re.as_str()));
         }
 
         // Check if the user input looks like a table.column pattern
@@ -153,22 +170,23 @@ pub fn is_common_sql_string(user_input: &str) -> bool {
         let looks_like_table_column =
             regex!(r"^(\.[a-z_][a-z0-9_]*|[a-z_][a-z0-9_]*\.|[a-z_][a-z0-9_]*\.[a-z_][a-z0-9_]*)$");
 
-        return looks_like_table_column.is_match(user_input);
+        return looks_like_table_column
+            .is_match(user_input)
+            .then(|| SqlExemption::built_in(looks_like_table_column.as_str()));
     }
 
     // Allow integers like `1`, `-1` or `-2`
     // We have to be careful with minus signs, as they can be used for SQL injections
-    if regex!(r"^-?[0-9]+$").is_match(user_input) {
-        return true;
+    let re = regex!(r"^-?[0-9]+$");
+    if re.is_match(user_input) {
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
     // Allow strings with letters, digits and spaces that end with a comma,
-    if user_input.len() <= 40
-        && user_input.ends_with(',')
-        && regex!(r"^[a-z0-9 ]+,$").is_match(user_input)
-    {
-        return true;
+    let re = regex!(r"^[a-z0-9 ]+,$");
+    if user_input.len() <= 40 && user_input.ends_with(',') && re.is_match(user_input) {
+        return Some(SqlExemption::built_in(re.as_str()));
     }
 
-    false
+    None
 }
@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::sql_injection::is_common_sql_string::is_common_sql_string;
-    use crate::sql_injection::is_common_sql_string::COMMON_SQL_STRINGS;
+    use crate::sql_injection::custom_exemptions::SqlExemption;
+    use crate::sql_injection::is_common_sql_string::{find_exemption, COMMON_SQL_STRINGS};
+
+    fn is_common_sql_string(user_input: &str) -> bool {
+        find_exemption(user_input).is_some()
+    }
 
     #[test]
     fn common_sql_strings_are_lowercase() {
@@ -441,4 +445,21 @@ mod tests {
         let over_limit = format!("{}a,", "a".repeat(39));
         assert!(!is_common_sql_string(&over_limit));
     }
+
+    #[test]
+    fn test_find_exemption_reports_built_in_rule() {
+        assert_eq!(
+            find_exemption("order by"),
+            Some(SqlExemption::built_in("order by"))
+        );
+        assert_eq!(
+            find_exemption("column_name asc"),
+            Some(SqlExemption::built_in(r"^[a-z_][a-z0-9_]* +(asc|desc)$"))
+        );
+        assert_eq!(
+            find_exemption("16.2"),
+            Some(SqlExemption::built_in(r"^-?[0-9]+\.[0-9]+$"))
+        );
+        assert_eq!(find_exemption("1 or 1=1"), None);
+    }
 }
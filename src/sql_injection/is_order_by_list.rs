@@ -0,0 +1,81 @@
+use super::helpers::find_case_insensitive::CaseInsensitiveStr;
+use super::helpers::select_dialect_based_on_enum::select_dialect_based_on_enum;
+use super::tokenize_query::tokenize_query;
+use sqlparser::ast::Expr;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Whitespace};
+
+/* Returns whether the user input is a list of columns to sort by that is used as the ORDER BY
+ * clause of the query, e.g. `name asc, created_at desc` (JSON:API and Ghost pass sort lists like
+ * this) in `SELECT * FROM posts ORDER BY name asc, created_at desc LIMIT 10`.
+ * The user input may only contain (qualified) column names, ASC/DESC and NULLS FIRST/LAST, and
+ * every occurrence of the user input in the query has to directly follow `ORDER BY`.
+ */
+pub fn is_order_by_list_in_query(
+    query: &CaseInsensitiveStr,
+    userinput_raw: &str,
+    dialect: i32,
+) -> bool {
+    let userinput = userinput_raw.trim();
+    if !is_order_by_list(userinput, dialect) {
+        return false;
+    }
+
+    let query_raw = query.original();
+    let occurrences = query.find_all(userinput);
+    !occurrences.is_empty()
+        && occurrences
+            .iter()
+            .all(|occurrence| is_preceded_by_order_by(&query_raw[..occurrence.start], dialect))
+}
+
+pub fn is_order_by_list(userinput: &str, dialect: i32) -> bool {
+    // The parser skips comments, but e.g. `name --` would comment out the rest of the query.
+    let contains_comment = tokenize_query(userinput, dialect).iter().any(|token| {
+        matches!(
+            token,
+            Token::Whitespace(Whitespace::SingleLineComment { .. })
+                | Token::Whitespace(Whitespace::MultiLineComment(_))
+        )
+    });
+    if contains_comment {
+        return false;
+    }
+
+    let dialect = select_dialect_based_on_enum(dialect);
+    let Ok(mut parser) = Parser::new(dialect.as_ref()).try_with_sql(userinput) else {
+        return false;
+    };
+    let Ok(order_by_exprs) = parser.parse_comma_separated(Parser::parse_order_by_expr) else {
+        return false;
+    };
+
+    // The whole user input has to be the ORDER BY list, e.g. not `name asc LIMIT 1`.
+    if parser.peek_token().token != Token::EOF {
+        return false;
+    }
+
+    order_by_exprs.iter().all(|order_by_expr| {
+        order_by_expr.with_fill.is_none()
+            && matches!(
+                order_by_expr.expr,
+                Expr::Identifier(_) | Expr::CompoundIdentifier(_)
+            )
+    })
+}
+
+// Checks if the last two tokens (ignoring whitespace and comments) are the keywords ORDER BY.
+fn is_preceded_by_order_by(query_before: &str, dialect: i32) -> bool {
+    let tokens = tokenize_query(query_before, dialect);
+    let mut keywords = tokens
+        .iter()
+        .rev()
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+        .map(|token| match token {
+            Token::Word(word) => word.keyword,
+            _ => Keyword::NoKeyword,
+        });
+
+    keywords.next() == Some(Keyword::BY) && keywords.next() == Some(Keyword::ORDER)
+}
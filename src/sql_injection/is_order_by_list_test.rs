@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::helpers::find_case_insensitive::CaseInsensitiveStr;
+    use crate::sql_injection::is_order_by_list::is_order_by_list_in_query;
+
+    macro_rules! order_by_list {
+        ($query:expr, $input:expr) => {
+            for dialect in [0, 3, 7, 8, 9, 12, 13] {
+                assert!(
+                    is_order_by_list_in_query(&CaseInsensitiveStr::new($query), $input, dialect),
+                    "should be an ORDER BY list\nquery: {}\ninput: {}\ndialect: {}\n",
+                    $query,
+                    $input,
+                    dialect
+                );
+            }
+        };
+    }
+
+    macro_rules! not_order_by_list {
+        ($query:expr, $input:expr) => {
+            for dialect in [0, 3, 7, 8, 9, 12, 13] {
+                assert!(
+                    !is_order_by_list_in_query(&CaseInsensitiveStr::new($query), $input, dialect),
+                    "should not be an ORDER BY list\nquery: {}\ninput: {}\ndialect: {}\n",
+                    $query,
+                    $input,
+                    dialect
+                );
+            }
+        };
+    }
+
+    #[test]
+    fn test_order_by_lists() {
+        order_by_list!(
+            "SELECT * FROM posts ORDER BY name asc, created_at desc",
+            "name asc, created_at desc"
+        );
+        order_by_list!(
+            "SELECT * FROM posts ORDER BY name asc, created_at desc LIMIT 10",
+            "name asc, created_at desc"
+        );
+        order_by_list!(
+            "SELECT * FROM posts p ORDER BY p.name, p.created_at DESC",
+            "p.name, p.created_at DESC"
+        );
+        order_by_list!(
+            "SELECT * FROM posts ORDER BY\n  published_at DESC NULLS LAST, id",
+            "published_at DESC NULLS LAST, id"
+        );
+        order_by_list!(
+            "SELECT * FROM posts ORDER /* sort */ BY name, id",
+            " name, id "
+        );
+        order_by_list!(
+            "SELECT * FROM (SELECT * FROM posts ORDER BY name, id) AS p",
+            "name, id"
+        );
+    }
+
+    #[test]
+    fn test_not_order_by_lists() {
+        not_order_by_list!(
+            "SELECT * FROM posts ORDER BY name asc, 1=1",
+            "name asc, 1=1"
+        );
+        not_order_by_list!(
+            "SELECT * FROM posts ORDER BY name asc LIMIT 1",
+            "name asc LIMIT 1"
+        );
+        not_order_by_list!(
+            "SELECT * FROM posts ORDER BY name, (SELECT password FROM users)",
+            "name, (SELECT password FROM users)"
+        );
+        not_order_by_list!(
+            "SELECT * FROM posts ORDER BY name; DROP TABLE posts",
+            "name; DROP TABLE posts"
+        );
+        not_order_by_list!("SELECT * FROM posts ORDER BY name -- , id", "name -- , id");
+        not_order_by_list!(
+            "SELECT * FROM posts ORDER BY lower(name), id",
+            "lower(name), id"
+        );
+    }
+
+    #[test]
+    fn test_order_by_list_outside_of_order_by_clause() {
+        not_order_by_list!("SELECT name, id FROM posts ORDER BY name, id", "name, id");
+        not_order_by_list!("SELECT name, id FROM posts", "name, id");
+        not_order_by_list!(
+            "SELECT * FROM posts WHERE title = 'name, id' ORDER BY name, id",
+            "name, id"
+        );
+        not_order_by_list!("SELECT * FROM posts -- ORDER BY\n name, id", "name, id");
+        not_order_by_list!("SELECT * FROM posts ORDER BY id", "name, id");
+    }
+}
@@ -1,11 +1,26 @@
 pub mod detect_sql_injection;
 pub mod detect_sql_injection_test;
 
+pub mod explain_sql_injection;
+pub mod explain_sql_injection_test;
+
+pub mod sql_injection_detector;
+pub mod sql_injection_detector_test;
+
 pub mod helpers;
 
 pub mod is_common_sql_string;
 pub mod is_common_sql_string_test;
 
+pub mod custom_exemptions;
+pub mod custom_exemptions_test;
+
+pub mod is_order_by_list;
+pub mod is_order_by_list_test;
+
+pub mod user_input_context;
+pub mod user_input_context_test;
+
 pub mod tokenize_query;
 pub mod tokenize_query_test;
 
@@ -13,3 +28,12 @@ pub mod filter_for_comment_tokens;
 
 pub mod have_comments_changed;
 pub mod have_comments_changed_test;
+
+pub mod have_token_categories_changed;
+pub mod have_token_categories_changed_test;
+
+pub mod user_input_decoding;
+pub mod user_input_decoding_test;
+
+pub mod sql_dialect;
+pub mod sql_dialect_test;
@@ -0,0 +1,99 @@
+use serde::Serialize;
+use sqlparser::dialect::*;
+use std::str::FromStr;
+
+/* The SQL dialects that are supported, the numbers are the ones used by the bindings.
+ * The names (and aliases) are parsed case-insensitively with `FromStr`, e.g. "postgres".
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[repr(i32)]
+pub enum SqlDialect {
+    Generic = 0,
+    Ansi = 1,
+    BigQuery = 2,
+    ClickHouse = 3,
+    Databricks = 4,
+    DuckDb = 5,
+    Hive = 6,
+    MsSql = 7,
+    MySql = 8,
+    PostgreSql = 9,
+    Redshift = 10,
+    Snowflake = 11,
+    SQLite = 12,
+    Oracle = 13,
+    // There is no MariaDB dialect in sqlparser, MariaDB uses the MySQL syntax.
+    MariaDb = 14,
+}
+
+impl SqlDialect {
+    pub fn to_dialect(self) -> Box<dyn Dialect> {
+        match self {
+            SqlDialect::Generic => Box::new(GenericDialect {}),
+            SqlDialect::Ansi => Box::new(AnsiDialect {}),
+            SqlDialect::BigQuery => Box::new(BigQueryDialect {}),
+            SqlDialect::ClickHouse => Box::new(ClickHouseDialect {}),
+            SqlDialect::Databricks => Box::new(DatabricksDialect {}),
+            SqlDialect::DuckDb => Box::new(DuckDbDialect {}),
+            SqlDialect::Hive => Box::new(HiveDialect {}),
+            SqlDialect::MsSql => Box::new(MsSqlDialect {}),
+            SqlDialect::MySql => Box::new(MySqlDialect {}),
+            SqlDialect::PostgreSql => Box::new(PostgreSqlDialect {}),
+            SqlDialect::Redshift => Box::new(RedshiftSqlDialect {}),
+            SqlDialect::Snowflake => Box::new(SnowflakeDialect {}),
+            SqlDialect::SQLite => Box::new(SQLiteDialect {}),
+            SqlDialect::Oracle => Box::new(OracleDialect {}),
+            SqlDialect::MariaDb => Box::new(MySqlDialect {}),
+        }
+    }
+}
+
+impl TryFrom<i32> for SqlDialect {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SqlDialect::Generic),
+            1 => Ok(SqlDialect::Ansi),
+            2 => Ok(SqlDialect::BigQuery),
+            3 => Ok(SqlDialect::ClickHouse),
+            4 => Ok(SqlDialect::Databricks),
+            5 => Ok(SqlDialect::DuckDb),
+            6 => Ok(SqlDialect::Hive),
+            7 => Ok(SqlDialect::MsSql),
+            8 => Ok(SqlDialect::MySql),
+            9 => Ok(SqlDialect::PostgreSql),
+            10 => Ok(SqlDialect::Redshift),
+            11 => Ok(SqlDialect::Snowflake),
+            12 => Ok(SqlDialect::SQLite),
+            13 => Ok(SqlDialect::Oracle),
+            14 => Ok(SqlDialect::MariaDb),
+            _ => Err(format!("Unknown SQL dialect: {}", value)),
+        }
+    }
+}
+
+impl FromStr for SqlDialect {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_lowercase().as_str() {
+            "generic" => Ok(SqlDialect::Generic),
+            "ansi" => Ok(SqlDialect::Ansi),
+            "bigquery" => Ok(SqlDialect::BigQuery),
+            "clickhouse" => Ok(SqlDialect::ClickHouse),
+            "databricks" => Ok(SqlDialect::Databricks),
+            "duckdb" => Ok(SqlDialect::DuckDb),
+            "hive" => Ok(SqlDialect::Hive),
+            "mssql" | "sqlserver" | "tsql" => Ok(SqlDialect::MsSql),
+            "mysql" => Ok(SqlDialect::MySql),
+            "postgres" | "postgresql" | "pg" => Ok(SqlDialect::PostgreSql),
+            "redshift" => Ok(SqlDialect::Redshift),
+            "snowflake" => Ok(SqlDialect::Snowflake),
+            "sqlite" => Ok(SqlDialect::SQLite),
+            "oracle" => Ok(SqlDialect::Oracle),
+            "mariadb" => Ok(SqlDialect::MariaDb),
+            _ => Err(format!("Unknown SQL dialect: {}", name)),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::sql_dialect::SqlDialect;
+    use sqlparser::dialect::{MySqlDialect, OracleDialect, PostgreSqlDialect, SQLiteDialect};
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("postgres".parse(), Ok(SqlDialect::PostgreSql));
+        assert_eq!("PostgreSQL".parse(), Ok(SqlDialect::PostgreSql));
+        assert_eq!("mysql".parse(), Ok(SqlDialect::MySql));
+        assert_eq!("mariadb".parse(), Ok(SqlDialect::MariaDb));
+        assert_eq!("Oracle".parse(), Ok(SqlDialect::Oracle));
+        assert_eq!("mssql".parse(), Ok(SqlDialect::MsSql));
+        assert_eq!("sqlserver".parse(), Ok(SqlDialect::MsSql));
+        assert_eq!(" sqlite ".parse(), Ok(SqlDialect::SQLite));
+        assert_eq!("generic".parse(), Ok(SqlDialect::Generic));
+    }
+
+    #[test]
+    fn test_from_str_unknown_dialect() {
+        assert_eq!(
+            "postgress".parse::<SqlDialect>(),
+            Err("Unknown SQL dialect: postgress".to_string())
+        );
+        assert!("".parse::<SqlDialect>().is_err());
+    }
+
+    #[test]
+    fn test_try_from_i32() {
+        for value in 0..=14 {
+            assert_eq!(SqlDialect::try_from(value).unwrap() as i32, value);
+        }
+        assert_eq!(SqlDialect::try_from(9), Ok(SqlDialect::PostgreSql));
+        assert_eq!(
+            SqlDialect::try_from(42),
+            Err("Unknown SQL dialect: 42".to_string())
+        );
+        assert!(SqlDialect::try_from(-1).is_err());
+    }
+
+    #[test]
+    fn test_to_dialect() {
+        assert!(SqlDialect::PostgreSql
+            .to_dialect()
+            .is::<PostgreSqlDialect>());
+        assert!(SqlDialect::MySql.to_dialect().is::<MySqlDialect>());
+        assert!(SqlDialect::SQLite.to_dialect().is::<SQLiteDialect>());
+        assert!(SqlDialect::Oracle.to_dialect().is::<OracleDialect>());
+        assert!(SqlDialect::MariaDb.to_dialect().is::<MySqlDialect>());
+    }
+}
@@ -0,0 +1,140 @@
+use super::detect_sql_injection::{
+    detect_sql_injection_str, detect_sql_injection_with_tokens, SqlInjectionDetectionResult,
+};
+use super::helpers::find_case_insensitive::CaseInsensitiveStr;
+use sqlparser::tokenizer::Token;
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+// Longer queries are not cached, so the memory of the cache stays bounded.
+pub const MAX_CACHED_QUERY_LEN: usize = 16 * 1024;
+
+/* Applications execute the same query (template) over and over again, with only the user input
+ * changing in the parameters. The detector keeps the lowercased query and the tokens of the most
+ * recently used queries (per dialect), so that only the query without the user input has to be
+ * tokenized :
+ * let detector = SqlInjectionDetector::new(256);
+ * detector.detect("SELECT * FROM users WHERE id = 1", "1", 9);
+ * The detector can be shared between threads.
+ */
+pub struct SqlInjectionDetector {
+    cache: Mutex<QueryCache>,
+}
+
+impl SqlInjectionDetector {
+    // `capacity` is the maximum amount of cached queries, 0 disables the cache.
+    pub fn new(capacity: usize) -> Self {
+        SqlInjectionDetector {
+            cache: Mutex::new(QueryCache::new(capacity)),
+        }
+    }
+
+    // Gives the same results as detect_sql_injection_str.
+    pub fn detect(
+        &self,
+        query_raw: &str,
+        userinput_raw: &str,
+        dialect: i32,
+    ) -> SqlInjectionDetectionResult {
+        if query_raw.len() > MAX_CACHED_QUERY_LEN {
+            return detect_sql_injection_str(query_raw, userinput_raw, dialect);
+        }
+
+        // The query is taken out of the cache while detecting, so the lock is not held for long.
+        let (key, query, mut tokens) = match self.lock_cache().take(query_raw, dialect) {
+            Some(cached) => (
+                Some(cached.query),
+                CaseInsensitiveStr::with_lowercased(query_raw, cached.lowercased),
+                Some(cached.tokens),
+            ),
+            None => (None, CaseInsensitiveStr::new(query_raw), None),
+        };
+        let detection_results =
+            detect_sql_injection_with_tokens(&query, userinput_raw, dialect, &mut tokens);
+        if let Some(tokens) = tokens {
+            self.lock_cache().insert(CachedQuery {
+                query: key.unwrap_or_else(|| query_raw.into()),
+                dialect,
+                lowercased: query.into_lowercased(),
+                tokens,
+            });
+        }
+
+        detection_results
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, QueryCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+pub struct CachedQuery {
+    pub query: Box<str>,
+    pub dialect: i32,
+    pub lowercased: String,
+    pub tokens: Vec<Token>,
+}
+
+/* Least recently used cache of queries, keyed on a hash of the query and the dialect, so that
+ * looking up a query doesn't allocate. The entries are ordered by the time they were last used,
+ * so the least recently used one is evicted without going over all entries.
+ */
+pub struct QueryCache {
+    capacity: usize,
+    hasher: RandomState,
+    entries: HashMap<u64, (CachedQuery, u64)>,
+    // The hashes of the entries by the time they were last used (incremented on every insert).
+    last_used: BTreeMap<u64, u64>,
+    clock: u64,
+}
+
+impl QueryCache {
+    pub fn new(capacity: usize) -> Self {
+        QueryCache {
+            capacity,
+            hasher: RandomState::new(),
+            entries: HashMap::new(),
+            last_used: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    // Removes the query from the cache, insert it again after using it.
+    pub fn take(&mut self, query: &str, dialect: i32) -> Option<CachedQuery> {
+        let hash = self.hasher.hash_one((query, dialect));
+        let (cached, _) = self.entries.get(&hash)?;
+        if &*cached.query != query || cached.dialect != dialect {
+            // Another query with the same hash.
+            return None;
+        }
+
+        self.remove(hash)
+    }
+
+    pub fn insert(&mut self, cached: CachedQuery) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        // Replaces the query itself, or another query with the same hash.
+        let hash = self.hasher.hash_one((&*cached.query, cached.dialect));
+        self.remove(hash);
+        if self.entries.len() >= self.capacity {
+            if let Some((_, least_recently_used)) = self.last_used.pop_first() {
+                self.entries.remove(&least_recently_used);
+            }
+        }
+
+        self.clock += 1;
+        self.last_used.insert(self.clock, hash);
+        self.entries.insert(hash, (cached, self.clock));
+    }
+
+    fn remove(&mut self, hash: u64) -> Option<CachedQuery> {
+        let (cached, last_used) = self.entries.remove(&hash)?;
+        self.last_used.remove(&last_used);
+        Some(cached)
+    }
+}
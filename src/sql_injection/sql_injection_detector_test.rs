@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::detect_sql_injection::detect_sql_injection_str;
+    use crate::sql_injection::sql_injection_detector::{
+        CachedQuery, QueryCache, SqlInjectionDetector, MAX_CACHED_QUERY_LEN,
+    };
+    use crate::sql_injection::tokenize_query::tokenize_query;
+
+    fn cached(query: &str, dialect: i32) -> CachedQuery {
+        CachedQuery {
+            query: query.into(),
+            dialect,
+            lowercased: query.to_lowercase(),
+            tokens: tokenize_query(query, dialect),
+        }
+    }
+
+    #[test]
+    fn test_same_results_as_detect_sql_injection_str() {
+        let detector = SqlInjectionDetector::new(2);
+        let cases = [
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "' OR 1=1 -- ",
+                0,
+            ),
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "' OR 1=1 -- ",
+                0,
+            ),
+            ("SELECT * FROM users WHERE id = '' OR 1=1 -- '", "users", 0),
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "goodbye",
+                0,
+            ),
+            (
+                "SELECT * FROM users WHERE id = '' OR 1=1 -- '",
+                "' OR 1=1 -- ",
+                9,
+            ),
+            ("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 9),
+            (
+                "SELECT * FROM users WHERE id = 'hello world'",
+                "hello world",
+                8,
+            ),
+            ("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 9),
+            (
+                "SELECT * FROM users WHERE id = 'hello world'",
+                "hello world",
+                8,
+            ),
+            (
+                "SELECT * FROM users WHERE id = 'hello world",
+                "hello world",
+                8,
+            ),
+            (
+                "SELECT * FROM users WHERE id = 'hello world",
+                "hello world",
+                8,
+            ),
+        ];
+
+        for (query, userinput, dialect) in cases {
+            let expected = detect_sql_injection_str(query, userinput, dialect);
+            let result = detector.detect(query, userinput, dialect);
+            assert_eq!(
+                format!("{:?}", result),
+                format!("{:?}", expected),
+                "{} with user input {}",
+                query,
+                userinput
+            );
+        }
+
+        let long_query = format!(
+            "SELECT * FROM users WHERE id = '' OR 1=1 -- '{}",
+            " ".repeat(MAX_CACHED_QUERY_LEN)
+        );
+        assert!(detector.detect(&long_query, "' OR 1=1 -- ", 0).detected);
+        assert!(
+            SqlInjectionDetector::new(0)
+                .detect("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 9)
+                .detected
+        );
+    }
+
+    #[test]
+    fn test_query_cache_evicts_least_recently_used() {
+        let mut cache = QueryCache::new(2);
+        cache.insert(cached("SELECT 1", 0));
+        cache.insert(cached("SELECT 2", 0));
+
+        // Using SELECT 1 makes SELECT 2 the least recently used query.
+        let query = cache.take("SELECT 1", 0).unwrap();
+        assert_eq!(&*query.query, "SELECT 1");
+        assert_eq!(query.lowercased, "select 1");
+        assert_eq!(query.tokens, tokenize_query("SELECT 1", 0));
+        cache.insert(query);
+        cache.insert(cached("SELECT 3", 0));
+
+        assert!(cache.take("SELECT 2", 0).is_none());
+        assert!(cache.take("SELECT 1", 0).is_some());
+        assert!(cache.take("SELECT 3", 0).is_some());
+        assert!(cache.take("SELECT 3", 0).is_none());
+
+        // The dialect is part of the key.
+        cache.insert(cached("SELECT 1", 0));
+        assert!(cache.take("SELECT 1", 9).is_none());
+        assert!(cache.take("SELECT 1", 0).is_some());
+
+        // Inserting a query again replaces it.
+        cache.insert(cached("SELECT 1", 0));
+        cache.insert(cached("SELECT 1", 0));
+        cache.insert(cached("SELECT 2", 0));
+        assert!(cache.take("SELECT 1", 0).is_some());
+        assert!(cache.take("SELECT 2", 0).is_some());
+
+        let mut disabled_cache = QueryCache::new(0);
+        disabled_cache.insert(cached("SELECT 1", 0));
+        assert!(disabled_cache.take("SELECT 1", 0).is_none());
+    }
+
+    #[test]
+    fn test_query_cache_evicts_in_order_of_use() {
+        let mut cache = QueryCache::new(3);
+        for i in 0..10 {
+            let query = format!("SELECT {}", i);
+            cache.insert(cached(&query, 0));
+            // SELECT 0 is used after every insert, so it's never evicted.
+            let first = cache.take("SELECT 0", 0).unwrap();
+            cache.insert(first);
+        }
+
+        assert!(cache.take("SELECT 0", 0).is_some());
+        assert!(cache.take("SELECT 9", 0).is_some());
+        assert!(cache.take("SELECT 8", 0).is_some());
+        assert!(cache.take("SELECT 7", 0).is_none());
+    }
+}
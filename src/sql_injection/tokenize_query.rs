@@ -1,5 +1,6 @@
 use crate::sql_injection::helpers::select_dialect_based_on_enum::select_dialect_based_on_enum;
 use sqlparser::tokenizer::*;
+use std::ops::Range;
 
 pub fn tokenize_query(sql: &str, dialect: i32) -> Vec<Token> {
     let dialect = select_dialect_based_on_enum(dialect);
@@ -12,3 +13,42 @@ pub fn tokenize_query(sql: &str, dialect: i32) -> Vec<Token> {
     let mut tokenizer = Tokenizer::new(dialect.as_ref(), sql).with_unescape(false);
     tokenizer.tokenize().unwrap_or_default()
 }
+
+/* Same as tokenize_query, but also returns the byte offsets of every token in the query.
+ * sqlparser gives the line and column of a token (both starting at 1), where the column counts
+ * characters and not bytes.
+ */
+pub fn tokenize_query_with_spans(sql: &str, dialect: i32) -> Vec<(Token, Range<usize>)> {
+    let dialect = select_dialect_based_on_enum(dialect);
+    let mut tokenizer = Tokenizer::new(dialect.as_ref(), sql).with_unescape(false);
+    let tokens = tokenizer.tokenize_with_location().unwrap_or_default();
+
+    let mut char_offsets: Vec<usize> = sql.char_indices().map(|(offset, _)| offset).collect();
+    char_offsets.push(sql.len());
+    let mut line_starts: Vec<usize> = vec![0];
+    line_starts.extend(
+        sql.chars()
+            .enumerate()
+            .filter(|(_, c)| *c == '\n')
+            .map(|(index, _)| index + 1),
+    );
+
+    let to_offset = |location: Location| -> usize {
+        let line = (location.line as usize).saturating_sub(1);
+        let column = (location.column as usize).saturating_sub(1);
+        line_starts
+            .get(line)
+            .and_then(|line_start| char_offsets.get(line_start + column))
+            .copied()
+            .unwrap_or(sql.len())
+    };
+
+    tokens
+        .into_iter()
+        .map(|token| {
+            let start = to_offset(token.span.start);
+            let end = to_offset(token.span.end).max(start);
+            (token.token, start..end)
+        })
+        .collect()
+}
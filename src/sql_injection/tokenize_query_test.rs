@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::sql_injection::tokenize_query::tokenize_query;
+    use crate::sql_injection::tokenize_query::{tokenize_query, tokenize_query_with_spans};
     use sqlparser::keywords::Keyword;
     use sqlparser::tokenizer::{Token, Whitespace, Word};
 
@@ -141,4 +141,26 @@ mod tests {
     }
 
     // Add more tests for different SQL queries and dialects as needed
+
+    #[test]
+    fn test_tokenize_with_spans() {
+        let sql = "SELECT 'é'\n FROM users";
+        let tokens = tokenize_query_with_spans(sql, 0);
+
+        let spans: Vec<&str> = tokens.iter().map(|(_, span)| &sql[span.clone()]).collect();
+        assert_eq!(
+            spans,
+            vec!["SELECT", " ", "'é'", "\n", " ", "FROM", " ", "users"]
+        );
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|(token, _)| token.clone())
+                .collect::<Vec<Token>>(),
+            tokenize_query(sql, 0)
+        );
+        assert_eq!(tokens[2].1, 7..11);
+
+        assert!(tokenize_query_with_spans("SELECT 'abc", 0).is_empty());
+    }
 }
@@ -0,0 +1,171 @@
+use super::helpers::find_case_insensitive::find_case_insensitive;
+use super::is_order_by_list::is_order_by_list;
+use super::tokenize_query::tokenize_query_with_spans;
+use serde::Serialize;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, Whitespace};
+use std::ops::Range;
+
+const SPACE_CHAR: char = ' ';
+
+// Where an occurrence of the user input landed in the query.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum UserInputContext {
+    // Inside of a string literal, e.g. `'...'`, `E'...'` or `$$...$$`
+    StringLiteral,
+    // Inside of a quoted identifier, e.g. `"..."`, `` `...` `` or `[...]`
+    QuotedIdentifier,
+    // A (part of a) keyword or identifier, e.g. a column name
+    Identifier,
+    // A (part of a) number
+    Number,
+    // The number after LIMIT or OFFSET
+    LimitOffset,
+    // A list of columns directly after ORDER BY, e.g. `name asc, id desc` or `2, 1`
+    OrderBy,
+    // Inside of a comment
+    Comment,
+    // Anything else, e.g. the user input contains the quotes of a string or spans multiple tokens
+    Raw,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserInputOccurrence {
+    // Byte offsets of the occurrence in the query.
+    pub span: Range<usize>,
+    pub context: UserInputContext,
+}
+
+/* Returns the syntactic context of every occurrence of the user input (ignoring case) in the
+ * query, based on the tokens of the query. Agents can use this to apply stricter rules to user
+ * input that landed in a Raw position, e.g. with user input `1 OR 1=1` :
+ * `SELECT * FROM users WHERE id = 1 OR 1=1 AND name = '1 OR 1=1'`
+ *                                 ^^^^^^^^ Raw          ^^^^^^^^ StringLiteral
+ */
+pub fn get_user_input_contexts(
+    query_raw: &str,
+    userinput_raw: &str,
+    dialect: i32,
+) -> Result<Vec<UserInputOccurrence>, String> {
+    let tokens = tokenize_query_with_spans(query_raw, dialect);
+    if tokens.is_empty() {
+        return Err("Failed to tokenize query".to_string());
+    }
+
+    let trimmed_userinput_raw = userinput_raw.trim_matches(SPACE_CHAR);
+    Ok(find_case_insensitive(query_raw, trimmed_userinput_raw)
+        .into_iter()
+        .map(|span| UserInputOccurrence {
+            context: get_context(query_raw, &tokens, &span, dialect),
+            span,
+        })
+        .collect())
+}
+
+fn get_context(
+    query: &str,
+    tokens: &[(Token, Range<usize>)],
+    occurrence: &Range<usize>,
+    dialect: i32,
+) -> UserInputContext {
+    let overlapping: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, span))| span.start < occurrence.end && occurrence.start < span.end)
+        .map(|(index, _)| index)
+        .collect();
+    let Some(&first) = overlapping.first() else {
+        return UserInputContext::Raw;
+    };
+
+    let previous_keywords = previous_keywords(&tokens[..first]);
+    let follows = |keywords: &[Keyword]| previous_keywords.starts_with(keywords);
+
+    if overlapping.len() > 1 {
+        let is_sort_list = is_order_by_list(&query[occurrence.clone()], dialect)
+            || overlapping
+                .iter()
+                .all(|&index| is_positional_sort_token(&tokens[index].0));
+        if follows(&[Keyword::BY, Keyword::ORDER]) && is_sort_list {
+            return UserInputContext::OrderBy;
+        }
+        return UserInputContext::Raw;
+    }
+
+    let (token, span) = &tokens[first];
+    // The quotes (or the start of the comment) are not part of the user input.
+    let is_inside = span.start < occurrence.start && occurrence.end < span.end;
+
+    match token {
+        Token::Word(word) if word.quote_style.is_some() => {
+            if is_inside {
+                UserInputContext::QuotedIdentifier
+            } else {
+                UserInputContext::Raw
+            }
+        }
+        Token::Word(_) | Token::Number(_, _) if follows(&[Keyword::BY, Keyword::ORDER]) => {
+            UserInputContext::OrderBy
+        }
+        Token::Number(_, _) if follows(&[Keyword::LIMIT]) || follows(&[Keyword::OFFSET]) => {
+            UserInputContext::LimitOffset
+        }
+        Token::Word(_) => UserInputContext::Identifier,
+        Token::Number(_, _) => UserInputContext::Number,
+        Token::Whitespace(Whitespace::SingleLineComment { .. })
+        | Token::Whitespace(Whitespace::MultiLineComment(_))
+            if is_inside =>
+        {
+            UserInputContext::Comment
+        }
+        _ if is_inside && is_string_literal(token) => UserInputContext::StringLiteral,
+        _ => UserInputContext::Raw,
+    }
+}
+
+// The keywords before the token, from the last to the first (ignoring whitespace and comments).
+fn previous_keywords(tokens: &[(Token, Range<usize>)]) -> Vec<Keyword> {
+    tokens
+        .iter()
+        .rev()
+        .filter(|(token, _)| !matches!(token, Token::Whitespace(_)))
+        .take(2)
+        .map(|(token, _)| match token {
+            Token::Word(word) if word.quote_style.is_none() => word.keyword,
+            _ => Keyword::NoKeyword,
+        })
+        .collect()
+}
+
+// e.g. `2, 1` in `ORDER BY 2, 1`
+fn is_positional_sort_token(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Number(_, _) | Token::Comma | Token::Whitespace(Whitespace::Space)
+    )
+}
+
+fn is_string_literal(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::SingleQuotedString(_)
+            | Token::DoubleQuotedString(_)
+            | Token::TripleSingleQuotedString(_)
+            | Token::TripleDoubleQuotedString(_)
+            | Token::DollarQuotedString(_)
+            | Token::SingleQuotedByteStringLiteral(_)
+            | Token::DoubleQuotedByteStringLiteral(_)
+            | Token::TripleSingleQuotedByteStringLiteral(_)
+            | Token::TripleDoubleQuotedByteStringLiteral(_)
+            | Token::SingleQuotedRawStringLiteral(_)
+            | Token::DoubleQuotedRawStringLiteral(_)
+            | Token::TripleSingleQuotedRawStringLiteral(_)
+            | Token::TripleDoubleQuotedRawStringLiteral(_)
+            | Token::NationalStringLiteral(_)
+            | Token::QuoteDelimitedStringLiteral(_)
+            | Token::NationalQuoteDelimitedStringLiteral(_)
+            | Token::EscapedStringLiteral(_)
+            | Token::UnicodeStringLiteral(_)
+            | Token::HexStringLiteral(_)
+    )
+}
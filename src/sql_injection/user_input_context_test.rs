@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::user_input_context::{get_user_input_contexts, UserInputContext};
+
+    fn contexts(query: &str, userinput: &str, dialect: i32) -> Vec<UserInputContext> {
+        get_user_input_contexts(query, userinput, dialect)
+            .unwrap()
+            .into_iter()
+            .map(|occurrence| occurrence.context)
+            .collect()
+    }
+
+    #[test]
+    fn test_string_literals() {
+        assert_eq!(
+            contexts("SELECT * FROM users WHERE name = 'John Doe'", "john doe", 0),
+            vec![UserInputContext::StringLiteral]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users WHERE name = $$John$$", "John", 9),
+            vec![UserInputContext::StringLiteral]
+        );
+        assert_eq!(
+            contexts(
+                "SELECT * FROM users WHERE name = E'John\\'s'",
+                "John\\'s",
+                9
+            ),
+            vec![UserInputContext::StringLiteral]
+        );
+        // The user input contains the quotes of the string.
+        assert_eq!(
+            contexts("SELECT * FROM users WHERE name = 'John'", "'John'", 0),
+            vec![UserInputContext::Raw]
+        );
+        assert_eq!(
+            contexts(
+                "SELECT * FROM users WHERE name = '' OR 1=1 -- '",
+                "' OR 1=1 -- ",
+                0
+            ),
+            vec![UserInputContext::Raw]
+        );
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(
+            contexts("SELECT \"first name\" FROM users", "first name", 9),
+            vec![UserInputContext::QuotedIdentifier]
+        );
+        assert_eq!(
+            contexts("SELECT `first name` FROM users", "first name", 8),
+            vec![UserInputContext::QuotedIdentifier]
+        );
+        assert_eq!(
+            contexts("SELECT [first name] FROM users", "first name", 7),
+            vec![UserInputContext::QuotedIdentifier]
+        );
+        assert_eq!(
+            contexts("SELECT first_name FROM users", "first_name", 0),
+            vec![UserInputContext::Identifier]
+        );
+        assert_eq!(
+            contexts("SELECT first_name FROM users", "name", 0),
+            vec![UserInputContext::Identifier]
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            contexts("SELECT * FROM users WHERE id = 123", "123", 0),
+            vec![UserInputContext::Number]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users LIMIT 10 OFFSET 20", "10", 0),
+            vec![UserInputContext::LimitOffset]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users LIMIT 10 OFFSET /* page */ 20", "20", 0),
+            vec![UserInputContext::LimitOffset]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users LIMIT 10 OR 1=1", "10 OR 1=1", 0),
+            vec![UserInputContext::Raw]
+        );
+    }
+
+    #[test]
+    fn test_order_by() {
+        assert_eq!(
+            contexts(
+                "SELECT * FROM users ORDER BY name asc, id desc LIMIT 10",
+                "name asc, id desc",
+                0
+            ),
+            vec![UserInputContext::OrderBy]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users ORDER BY name", "name", 0),
+            vec![UserInputContext::OrderBy]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users ORDER BY 2", "2", 0),
+            vec![UserInputContext::OrderBy]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users ORDER BY 2, 1", "2, 1", 0),
+            vec![UserInputContext::OrderBy]
+        );
+    }
+
+    #[test]
+    fn test_raw_after_order_by() {
+        assert_eq!(
+            contexts(
+                "SELECT * FROM users ORDER BY name; DROP TABLE users",
+                "name; DROP TABLE users",
+                0
+            ),
+            vec![UserInputContext::Raw]
+        );
+        assert_eq!(
+            contexts(
+                "SELECT * FROM users ORDER BY 1 UNION SELECT password FROM admins",
+                "1 UNION SELECT password FROM admins",
+                0
+            ),
+            vec![UserInputContext::Raw]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users ORDER BY name -- asc", "name -- asc", 0),
+            vec![UserInputContext::Raw]
+        );
+    }
+
+    #[test]
+    fn test_comments_and_raw() {
+        assert_eq!(
+            contexts("SELECT * FROM users -- hello world\n", "hello world", 0),
+            vec![UserInputContext::Comment]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users /* hello world */", "hello world", 0),
+            vec![UserInputContext::Comment]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users WHERE id = 1 OR 1=1", "1 OR 1=1", 0),
+            vec![UserInputContext::Raw]
+        );
+        assert_eq!(
+            contexts("SELECT * FROM users WHERE id = 1 -- a", "1 -- a", 0),
+            vec![UserInputContext::Raw]
+        );
+    }
+
+    #[test]
+    fn test_every_occurrence() {
+        let query = "SELECT * FROM users WHERE id = 1 OR 1=1 AND name = '1 OR 1=1'";
+        let occurrences = get_user_input_contexts(query, " 1 or 1=1 ", 0).unwrap();
+        assert_eq!(occurrences.len(), 2);
+        assert_eq!(occurrences[0].span, 31..39);
+        assert_eq!(occurrences[0].context, UserInputContext::Raw);
+        assert_eq!(occurrences[1].span, 52..60);
+        assert_eq!(occurrences[1].context, UserInputContext::StringLiteral);
+        assert_eq!(
+            serde_json::to_value(&occurrences).unwrap(),
+            serde_json::json!([
+                { "span": { "start": 31, "end": 39 }, "context": "Raw" },
+                { "span": { "start": 52, "end": 60 }, "context": "StringLiteral" }
+            ])
+        );
+
+        assert!(get_user_input_contexts(query, "nope", 0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            get_user_input_contexts("SELECT 'abc", "abc", 0).unwrap_err(),
+            "Failed to tokenize query"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use crate::helpers::html_entity_decode::html_entity_decode;
+use crate::helpers::percent_decode::percent_decode;
+use serde::Serialize;
+
+/* Transformations that frameworks apply to user input before it ends up in a query, while the
+ * agent only sees the raw user input. Each decoding turns the raw user input into the form that
+ * might occur in the query.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum UserInputDecoding {
+    // `%27%20OR%201%3D1` -> `' OR 1=1`
+    UrlDecode,
+    // `&#39; OR 1=1` -> `' OR 1=1`
+    HtmlEntityDecode,
+    // `' OR \"a\"=\"a\"` -> `' OR "a"="a"`
+    JsonUnescape,
+    // `' OR 1=1` -> `\' OR 1=1` (e.g. addslashes in PHP)
+    BackslashEscapeQuotes,
+}
+
+impl UserInputDecoding {
+    // Returns None if the user input can't be decoded.
+    pub fn decode(self, userinput: &str) -> Option<String> {
+        match self {
+            UserInputDecoding::UrlDecode => percent_decode(userinput),
+            UserInputDecoding::HtmlEntityDecode => Some(html_entity_decode(userinput)),
+            UserInputDecoding::JsonUnescape => {
+                serde_json::from_str::<String>(&format!("\"{}\"", userinput)).ok()
+            }
+            UserInputDecoding::BackslashEscapeQuotes => Some(
+                userinput
+                    .replace('\\', "\\\\")
+                    .replace('\'', "\\'")
+                    .replace('"', "\\\""),
+            ),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_injection::user_input_decoding::UserInputDecoding;
+
+    fn decode(decoding: UserInputDecoding, userinput: &str) -> Option<String> {
+        decoding.decode(userinput)
+    }
+
+    #[test]
+    fn test_url_decode() {
+        assert_eq!(
+            decode(UserInputDecoding::UrlDecode, "%27%20OR%201%3D1"),
+            Some("' OR 1=1".to_string())
+        );
+        assert_eq!(decode(UserInputDecoding::UrlDecode, "%C3"), None);
+    }
+
+    #[test]
+    fn test_html_entity_decode() {
+        assert_eq!(
+            decode(
+                UserInputDecoding::HtmlEntityDecode,
+                "&#39; OR &quot;a&quot;=&quot;a&quot;"
+            ),
+            Some("' OR \"a\"=\"a\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_json_unescape() {
+        assert_eq!(
+            decode(UserInputDecoding::JsonUnescape, r#"' OR \"a\"=\"a\" --"#),
+            Some("' OR \"a\"=\"a\" --".to_string())
+        );
+        assert_eq!(decode(UserInputDecoding::JsonUnescape, r#"a"b"#), None);
+        assert_eq!(decode(UserInputDecoding::JsonUnescape, r#"a\"#), None);
+    }
+
+    #[test]
+    fn test_backslash_escape_quotes() {
+        assert_eq!(
+            decode(
+                UserInputDecoding::BackslashEscapeQuotes,
+                r#"' OR "a"="a" \"#
+            ),
+            Some(r#"\' OR \"a\"=\"a\" \\"#.to_string())
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct IpClassification {
+    pub is_private: bool,
+    pub is_loopback: bool,
+    pub is_link_local: bool,
+    pub is_metadata: bool,
+}
+
+// Cloud metadata services, e.g. AWS, GCP, Azure and Alibaba Cloud
+const METADATA_IPV4_ADDRESSES: [Ipv4Addr; 2] = [
+    Ipv4Addr::new(169, 254, 169, 254),
+    Ipv4Addr::new(100, 100, 100, 200),
+];
+
+// AWS metadata service over IPv6 (fd00:ec2::254)
+const METADATA_IPV6_ADDRESSES: [Ipv6Addr; 1] =
+    [Ipv6Addr::new(0xfd00, 0x0ec2, 0, 0, 0, 0, 0, 0x254)];
+
+pub const METADATA_HOSTNAMES: [&str; 2] = ["metadata.google.internal", "metadata.goog"];
+
+pub fn classify_ip_address(ip: &IpAddr) -> IpClassification {
+    match ip {
+        IpAddr::V4(ipv4) => classify_ipv4(ipv4),
+        IpAddr::V6(ipv6) => classify_ipv6(ipv6),
+    }
+}
+
+/* Private covers all ranges that are not reachable on the public internet, except for
+ * loopback and link-local which have their own flag :
+ * - 10.0.0.0/8, 172.16.0.0/12, 192.168.0.0/16 (RFC 1918)
+ * - 100.64.0.0/10 (carrier-grade NAT)
+ * - 0.0.0.0/8 (0.0.0.0 connects to localhost on most systems)
+ * - 192.0.0.0/24, 198.18.0.0/15 (IETF protocol assignments, benchmarking)
+ * - 240.0.0.0/4 (reserved, including the broadcast address)
+ */
+fn classify_ipv4(ip: &Ipv4Addr) -> IpClassification {
+    let [a, b, c, _] = ip.octets();
+    IpClassification {
+        is_private: ip.is_private()
+            || (a == 100 && (64..128).contains(&b))
+            || a == 0
+            || (a == 192 && b == 0 && c == 0)
+            || (a == 198 && (b == 18 || b == 19))
+            || a >= 240,
+        is_loopback: ip.is_loopback(),
+        is_link_local: ip.is_link_local(),
+        is_metadata: METADATA_IPV4_ADDRESSES.contains(ip),
+    }
+}
+
+/* IPv6 addresses that embed an IPv4 address are classified as the IPv4 address they contain
+ * (see embedded_ipv4). Private covers :
+ * - fc00::/7 (unique local addresses)
+ * - fec0::/10 (deprecated site-local addresses)
+ * - :: (unspecified, connects to localhost on most systems)
+ */
+fn classify_ipv6(ip: &Ipv6Addr) -> IpClassification {
+    if let Some(ipv4) = embedded_ipv4(ip) {
+        return classify_ipv4(&ipv4);
+    }
+
+    let first_segment = ip.segments()[0];
+    IpClassification {
+        is_private: (first_segment & 0xfe00) == 0xfc00
+            || (first_segment & 0xffc0) == 0xfec0
+            || ip.is_unspecified(),
+        is_loopback: ip.is_loopback(),
+        is_link_local: (first_segment & 0xffc0) == 0xfe80,
+        is_metadata: METADATA_IPV6_ADDRESSES.contains(ip),
+    }
+}
+
+/* Returns the IPv4 address that is embedded in the IPv6 address :
+ * - IPv4-mapped addresses, e.g. `::ffff:169.254.169.254`
+ * - IPv4-compatible addresses (deprecated), e.g. `::169.254.169.254`
+ *   `::` and `::1` are not, they are the unspecified and loopback address of IPv6.
+ * - NAT64 addresses with the well-known prefix 64:ff9b::/96, e.g. `64:ff9b::a9fe:a9fe`, which a
+ *   NAT64 gateway translates to the IPv4 address
+ */
+fn embedded_ipv4(ip: &Ipv6Addr) -> Option<Ipv4Addr> {
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return Some(ipv4);
+    }
+
+    let [a, b, c, d, e, f, g, h] = ip.segments();
+    match [a, b, c, d, e, f] {
+        [0, 0, 0, 0, 0, 0] if !ip.is_unspecified() && !ip.is_loopback() => {}
+        [0x64, 0xff9b, 0, 0, 0, 0] => {}
+        _ => return None,
+    }
+
+    let [g1, g2] = g.to_be_bytes();
+    let [h1, h2] = h.to_be_bytes();
+    Some(Ipv4Addr::new(g1, g2, h1, h2))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssrf::classify_ip_address::{classify_ip_address, IpClassification};
+    use std::net::IpAddr;
+
+    fn classify(ip: &str) -> IpClassification {
+        classify_ip_address(&ip.parse::<IpAddr>().unwrap())
+    }
+
+    #[test]
+    fn test_public_ip_addresses() {
+        assert_eq!(classify("8.8.8.8"), IpClassification::default());
+        assert_eq!(classify("172.32.0.1"), IpClassification::default());
+        assert_eq!(classify("100.128.0.1"), IpClassification::default());
+        assert_eq!(
+            classify("2001:4860:4860::8888"),
+            IpClassification::default()
+        );
+    }
+
+    #[test]
+    fn test_private_ip_addresses() {
+        for ip in [
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "100.64.0.1",
+            "0.0.0.0",
+            "192.0.0.1",
+            "198.18.0.1",
+            "255.255.255.255",
+            "fd12:3456::1",
+            "fec0::1",
+            "::",
+            "::ffff:10.0.0.1",
+            "::10.0.0.1",
+            "64:ff9b::10.0.0.1",
+        ] {
+            assert!(classify(ip).is_private, "{} should be private", ip);
+        }
+    }
+
+    #[test]
+    fn test_loopback_ip_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "127.255.255.254",
+            "::1",
+            "::ffff:127.0.0.1",
+            "::127.0.0.1",
+            "64:ff9b::7f00:1",
+        ] {
+            assert!(classify(ip).is_loopback, "{} should be loopback", ip);
+        }
+    }
+
+    #[test]
+    fn test_link_local_ip_addresses() {
+        for ip in ["169.254.0.1", "fe80::1", "febf::1"] {
+            assert!(classify(ip).is_link_local, "{} should be link-local", ip);
+        }
+    }
+
+    #[test]
+    fn test_metadata_ip_addresses() {
+        for ip in [
+            "169.254.169.254",
+            "100.100.100.200",
+            "fd00:ec2::254",
+            "::ffff:169.254.169.254",
+            "::169.254.169.254",
+            "64:ff9b::a9fe:a9fe",
+            "64:ff9b::169.254.169.254",
+        ] {
+            assert!(classify(ip).is_metadata, "{} should be metadata", ip);
+        }
+        assert!(!classify("169.254.169.253").is_metadata);
+        assert!(!classify("64:ff9b:1::a9fe:a9fe").is_metadata);
+    }
+
+    #[test]
+    fn test_embedded_ipv4_addresses() {
+        assert_eq!(classify("64:ff9b::808:808"), IpClassification::default());
+        assert_eq!(classify("::8.8.8.8"), IpClassification::default());
+        assert!(classify("64:ff9b::a9fe:1").is_link_local);
+
+        // The unspecified and loopback address of IPv6 are not IPv4-compatible addresses.
+        assert_eq!(
+            classify("::"),
+            IpClassification {
+                is_private: true,
+                ..IpClassification::default()
+            }
+        );
+        assert_eq!(
+            classify("::1"),
+            IpClassification {
+                is_loopback: true,
+                ..IpClassification::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod classify_ip_address;
+pub mod parse_ipv4;
+
+pub mod ssrf_analyze_url;
+pub mod ssrf_analyze_url_test;
+
+mod url_analysis_result;
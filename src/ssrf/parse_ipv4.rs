@@ -0,0 +1,107 @@
+use std::net::Ipv4Addr;
+
+/* Parses a hostname as an IPv4 address the way browsers and most URL parsers do (WHATWG URL spec).
+ * Besides the regular dotted decimal notation, this accepts :
+ * - Fewer than 4 parts, the last part fills the remaining bytes (e.g. `127.1` or `2130706433`)
+ * - Hexadecimal parts (e.g. `0x7f.0.0.1`)
+ * - Octal parts with a leading zero (e.g. `0177.0.0.1`)
+ * Returns None if the hostname is not an IPv4 address.
+ */
+pub fn parse_ipv4(hostname: &str) -> Option<Ipv4Addr> {
+    let parts: Vec<&str> = hostname.split('.').collect();
+    if parts.is_empty() || parts.len() > 4 {
+        return None;
+    }
+
+    let numbers = parts
+        .iter()
+        .map(|part| parse_ipv4_number(part))
+        .collect::<Option<Vec<u64>>>()?;
+
+    let (last, leading) = numbers.split_last()?;
+    if leading.iter().any(|number| *number > 255) {
+        return None;
+    }
+
+    // The last part fills up all remaining bytes, e.g. `127.1` has `1` for the last 3 bytes.
+    let remaining_bytes = 5 - numbers.len() as u32;
+    if *last >= 256_u64.pow(remaining_bytes) {
+        return None;
+    }
+
+    let mut address = *last;
+    for (i, number) in leading.iter().enumerate() {
+        address += number << (8 * (3 - i));
+    }
+
+    Some(Ipv4Addr::from(address as u32))
+}
+
+fn parse_ipv4_number(part: &str) -> Option<u64> {
+    if part.is_empty() {
+        return None;
+    }
+
+    if let Some(hex) = part.strip_prefix("0x").or(part.strip_prefix("0X")) {
+        if hex.is_empty() {
+            return Some(0);
+        }
+        return parse_digits(hex, 16);
+    }
+
+    if part.len() > 1 && part.starts_with('0') {
+        return parse_digits(&part[1..], 8);
+    }
+
+    parse_digits(part, 10)
+}
+
+fn parse_digits(digits: &str, radix: u32) -> Option<u64> {
+    // from_str_radix also accepts a leading `+`, which is not a valid IPv4 number.
+    if !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+
+    u64::from_str_radix(digits, radix).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssrf::parse_ipv4::parse_ipv4;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_parse_ipv4() {
+        assert_eq!(parse_ipv4("127.0.0.1"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(parse_ipv4("127.1"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(parse_ipv4("127.0.1"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(parse_ipv4("2130706433"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(parse_ipv4("0x7f000001"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(parse_ipv4("0x7f.0.0.1"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(parse_ipv4("0177.0.0.1"), Some(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(
+            parse_ipv4("017700000001"),
+            Some(Ipv4Addr::new(127, 0, 0, 1))
+        );
+        assert_eq!(parse_ipv4("0"), Some(Ipv4Addr::new(0, 0, 0, 0)));
+        assert_eq!(parse_ipv4("0x"), Some(Ipv4Addr::new(0, 0, 0, 0)));
+        assert_eq!(
+            parse_ipv4("169.254.43518"),
+            Some(Ipv4Addr::new(169, 254, 169, 254))
+        );
+    }
+
+    #[test]
+    fn test_parse_ipv4_not_an_ip() {
+        assert_eq!(parse_ipv4("localhost"), None);
+        assert_eq!(parse_ipv4("example.com"), None);
+        assert_eq!(parse_ipv4("1.2.3.4.5"), None);
+        assert_eq!(parse_ipv4("256.0.0.1"), None);
+        assert_eq!(parse_ipv4("1.2.3.256"), None);
+        assert_eq!(parse_ipv4("4294967296"), None);
+        assert_eq!(parse_ipv4("08.0.0.1"), None);
+        assert_eq!(parse_ipv4("1..1"), None);
+        assert_eq!(parse_ipv4("+1.0.0.1"), None);
+        assert_eq!(parse_ipv4(""), None);
+    }
+}
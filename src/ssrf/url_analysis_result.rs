@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UrlAnalysisResult {
+    pub hostname: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    pub is_private: bool,
+    pub is_loopback: bool,
+    pub is_link_local: bool,
+    pub is_metadata: bool,
+}
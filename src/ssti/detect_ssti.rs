@@ -0,0 +1,40 @@
+use super::helpers::select_template_engine_based_on_enum::select_template_engine_based_on_enum;
+use super::tokenize_template::tokenize_template;
+use crate::helpers::have_tokens_changed::have_tokens_changed;
+
+/* Detects server-side template injections : the user input is part of the source of a template
+ * (not one of the values it's rendered with) and introduces new tags or expressions, e.g.
+ * `Hello {{constructor.constructor('return process')()}}` with Handlebars.
+ */
+pub fn detect_ssti_str(template: &str, userinput: &str, engine: i32) -> bool {
+    if userinput.len() <= 1 {
+        // We assume that a single character cannot be an injection.
+        return false;
+    }
+
+    if userinput.len() > template.len() {
+        // If the user input is longer than the template, it's not an injection.
+        return false;
+    }
+
+    if !template.contains(userinput) {
+        // If the template does not contain the user input, it's not an injection.
+        return false;
+    }
+
+    // Tokenize template :
+    let engine = select_template_engine_based_on_enum(engine);
+    let tokens = tokenize_template(template, engine);
+    if tokens.is_empty() {
+        // Tokens are empty, probably a parsing issue with original template, return false.
+        return false;
+    }
+
+    // Replace user input with string of equal length and tokenize again :
+    let safe_replace_str = "a".repeat(userinput.len());
+    let template_without_input = template.replace(userinput, &safe_replace_str);
+    let tokens_without_input = tokenize_template(&template_without_input, engine);
+
+    // If the structure of the template changed, the user input is interpreted as template syntax.
+    have_tokens_changed(&tokens, &tokens_without_input)
+}
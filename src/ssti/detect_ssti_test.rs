@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use crate::ssti::detect_ssti::detect_ssti_str;
+
+    macro_rules! is_injection {
+        ($template:expr, $input:expr, $engine:expr) => {
+            assert!(
+                detect_ssti_str($template, $input, $engine),
+                "should be an injection\ntemplate: {}\ninput: {}\n",
+                $template,
+                $input
+            )
+        };
+    }
+
+    macro_rules! not_injection {
+        ($template:expr, $input:expr, $engine:expr) => {
+            assert!(
+                !detect_ssti_str($template, $input, $engine),
+                "should not be an injection\ntemplate: {}\ninput: {}\n",
+                $template,
+                $input
+            )
+        };
+    }
+
+    #[test]
+    fn test_single_characters_are_ignored() {
+        not_injection!("Hello {", "{", 0);
+        not_injection!("Hello %", "%", 1);
+    }
+
+    #[test]
+    fn test_user_input_not_in_template() {
+        not_injection!("Hello {{name}}", "", 0);
+        not_injection!("Hello {{name}}", "{{7*7}}", 0);
+        not_injection!("Hi", "Hello world", 2);
+    }
+
+    #[test]
+    fn test_handlebars_injection() {
+        is_injection!("Hello {{7*7}}!", "{{7*7}}", 0);
+        is_injection!("Hello {{name}}{{evil}}!", "{{evil}}", 0);
+        is_injection!(
+            "Hello {{#with \"s\" as |string|}}{{string.constructor}}{{/with}}",
+            "{{#with \"s\" as |string|}}{{string.constructor}}{{/with}}",
+            0
+        );
+        is_injection!("Hello {{{html}}}", "{{{html}}}", 0);
+        is_injection!("Hello {{> partial}}", "{{> partial}}", 0);
+        is_injection!(
+            "Hello {{t \"a\"}}{{evil}}{{t \"b\"}}",
+            "a\"}}{{evil}}{{t \"b",
+            0
+        );
+        // A comment hides the rest of the template
+        is_injection!("Hello {{!-- --}}{{name}}", "{{!-- --}}", 0);
+    }
+
+    #[test]
+    fn test_handlebars_not_injection() {
+        not_injection!("Hello John Doe!", "John Doe", 0);
+        not_injection!("Hello {{name}}, you have 3 messages", "3 messages", 0);
+        not_injection!("Hello \\{{name}}", "\\{{name}}", 0);
+        not_injection!("Price: {10} or {{price}}", "{10}", 0);
+        not_injection!("Hello {{t \"John Doe\"}}", "John Doe", 0);
+        not_injection!("Hello {{user.name}}", "name", 0);
+        not_injection!("Hello {{! John Doe }}", "John Doe", 0);
+    }
+
+    #[test]
+    fn test_ejs_injection() {
+        is_injection!("Hello <%= 7*7 %>!", "<%= 7*7 %>", 1);
+        is_injection!(
+            "Hello <%= process.mainModule.require('child_process').execSync('id') %>",
+            "<%= process.mainModule.require('child_process').execSync('id') %>",
+            1
+        );
+        is_injection!("Hello <%- html %>", "<%- html %>", 1);
+        is_injection!("<% if (a) { %>Hi<% } %>", "<% if (a) { %>", 1);
+        is_injection!("Hello <%= \"a\" + evil + \"b\" %>", "a\" + evil + \"b", 1);
+        is_injection!("Hello <%= \"x%> <%= evil %>\" %>", "x%> <%= evil %>", 1);
+    }
+
+    #[test]
+    fn test_ejs_not_injection() {
+        not_injection!("Hello John Doe!", "John Doe", 1);
+        not_injection!("Discount: 50<%% off", "50<%% off", 1);
+        not_injection!("Hello <%= \"John Doe\" %>", "John Doe", 1);
+        not_injection!("Hello {{7*7}}", "{{7*7}}", 1);
+        not_injection!("Hello <%# John Doe %>", "John Doe", 1);
+    }
+
+    #[test]
+    fn test_jinja_injection() {
+        is_injection!("Hello {{7*7}}!", "{{7*7}}", 2);
+        is_injection!(
+            "Hello {{ self.__init__.__globals__.__builtins__ }}",
+            "{{ self.__init__.__globals__.__builtins__ }}",
+            2
+        );
+        is_injection!(
+            "Hello {% for x in ().__class__.__base__.__subclasses__() %}{% endfor %}",
+            "{% for x in ().__class__.__base__.__subclasses__() %}{% endfor %}",
+            2
+        );
+        is_injection!("Hello {{ 'a' ~ config ~ 'b' }}", "a' ~ config ~ 'b", 2);
+        is_injection!("Hello {# #}{{ name }}", "{# #}", 2);
+        is_injection!(
+            "{% raw %}{% endraw %}{{ config }}{% raw %}{% endraw %}",
+            "{% endraw %}{{ config }}{% raw %}",
+            2
+        );
+    }
+
+    #[test]
+    fn test_jinja_not_injection() {
+        not_injection!("Hello John Doe!", "John Doe", 2);
+        not_injection!("Hello {{ 'John Doe' }}", "John Doe", 2);
+        not_injection!("Hello {% raw %}{{ 7*7 }}{% endraw %}", "{{ 7*7 }}", 2);
+        not_injection!("Hello <%= 7*7 %>", "<%= 7*7 %>", 2);
+        not_injection!("Hello {{ name }}, welcome", "welcome", 2);
+        not_injection!("Hello {# John Doe #}", "John Doe", 2);
+    }
+
+    #[test]
+    fn test_unknown_engine_defaults_to_handlebars() {
+        is_injection!("Hello {{7*7}}!", "{{7*7}}", 42);
+        not_injection!("Hello <%= 7*7 %>!", "<%= 7*7 %>", 42);
+    }
+}
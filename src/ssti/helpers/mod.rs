@@ -0,0 +1 @@
+pub mod select_template_engine_based_on_enum;
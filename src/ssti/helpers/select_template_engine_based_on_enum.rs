@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateEngine {
+    Handlebars,
+    Ejs,
+    Jinja,
+}
+
+/*
+0 -> Handlebars / Mustache
+1 -> EJS
+2 -> Nunjucks / Jinja2
+Default -> Handlebars / Mustache
+*/
+pub fn select_template_engine_based_on_enum(enumerator: i32) -> TemplateEngine {
+    // 0 is Handlebars / Mustache.
+    match enumerator {
+        0 => TemplateEngine::Handlebars,
+        1 => TemplateEngine::Ejs,
+        2 => TemplateEngine::Jinja,
+        _ => TemplateEngine::Handlebars,
+    }
+}
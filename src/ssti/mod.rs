@@ -0,0 +1,7 @@
+pub mod detect_ssti;
+pub mod detect_ssti_test;
+
+pub mod tokenize_template;
+pub mod tokenize_template_test;
+
+pub mod helpers;
@@ -0,0 +1,294 @@
+use super::helpers::select_template_engine_based_on_enum::TemplateEngine;
+use crate::helpers::have_tokens_changed::StructuralToken;
+
+/*
+ * Tokenizer for the syntax of template engines (Handlebars / Mustache, EJS and
+ * Nunjucks / Jinja2). It does not render or fully parse the template, it only splits it into
+ * the tokens that define its structure :
+ * - Text outside of tags (including escaped tags like `\{{` or `<%%`)
+ * - The delimiters of tags (`{{`, `{{{`, `{%`, `<%=`, `%>`, ...)
+ * - Words (identifiers, keywords and numbers), strings and punctuation inside of tags
+ * - Comments (`{{! ... }}`, `{{!-- ... --}}`, `{# ... #}` and `<%# ... %>`)
+ * The content of raw blocks (`{% raw %}...{% endraw %}`) is text as well.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateToken {
+    Text(String),
+    TagOpen(String),
+    TagClose(String),
+    Word(String),
+    String(String),
+    Punctuation(String),
+    Comment(String),
+}
+
+impl StructuralToken for TemplateToken {
+    fn is_altered(&self, other: &Self) -> bool {
+        match (self, other) {
+            // e.g. `{{` became `{{{`
+            (TemplateToken::TagOpen(open1), TemplateToken::TagOpen(open2)) => open1 != open2,
+            (TemplateToken::TagClose(close1), TemplateToken::TagClose(close2)) => close1 != close2,
+            (
+                TemplateToken::Punctuation(punctuation1),
+                TemplateToken::Punctuation(punctuation2),
+            ) => punctuation1 != punctuation2,
+            (TemplateToken::Comment(comment1), TemplateToken::Comment(comment2)) => {
+                comment1.len() != comment2.len()
+            }
+            // Text, words and strings are allowed to change, that's where the user input ends up.
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct UnterminatedError;
+
+struct Syntax {
+    // The opening and closing delimiters, ordered so that the longest ones are matched first.
+    comments: &'static [(&'static str, &'static str)],
+    tags: &'static [(&'static str, &'static str)],
+    // Sequences that start like a tag, but are rendered as text.
+    escapes: &'static [&'static str],
+    quotes: &'static [char],
+    // EJS closes a tag at the first `%>`, even if it's inside of a string, e.g. `<%= "%>" %>`
+    close_inside_strings: bool,
+    // Jinja2 only closes a tag outside of brackets, e.g. `{{ {'a': {'b': 1}} }}`
+    balanced_brackets: bool,
+    // The `{% name %}` blocks whose content is text, until `{% endname %}`.
+    raw_blocks: &'static [&'static str],
+}
+
+const HANDLEBARS: Syntax = Syntax {
+    comments: &[("{{!--", "--}}"), ("{{!", "}}")],
+    tags: &[("{{{", "}}}"), ("{{", "}}")],
+    escapes: &["\\{{"],
+    quotes: &['\'', '"'],
+    close_inside_strings: false,
+    balanced_brackets: false,
+    raw_blocks: &[],
+};
+
+const EJS: Syntax = Syntax {
+    comments: &[("<%#", "%>")],
+    tags: &[("<%_", "%>"), ("<%=", "%>"), ("<%-", "%>"), ("<%", "%>")],
+    escapes: &["<%%"],
+    quotes: &['\'', '"', '`'],
+    close_inside_strings: true,
+    balanced_brackets: false,
+    raw_blocks: &[],
+};
+
+const JINJA: Syntax = Syntax {
+    comments: &[("{#", "#}")],
+    tags: &[("{{", "}}"), ("{%", "%}")],
+    escapes: &[],
+    quotes: &['\'', '"'],
+    close_inside_strings: false,
+    balanced_brackets: true,
+    raw_blocks: &["raw", "verbatim"],
+};
+
+pub fn tokenize_template(template: &str, engine: TemplateEngine) -> Vec<TemplateToken> {
+    let syntax = match engine {
+        TemplateEngine::Handlebars => &HANDLEBARS,
+        TemplateEngine::Ejs => &EJS,
+        TemplateEngine::Jinja => &JINJA,
+    };
+    let mut tokenizer = TemplateTokenizer {
+        chars: template.chars().collect(),
+        pos: 0,
+        text: String::new(),
+        tokens: Vec::new(),
+        syntax,
+    };
+
+    // An unterminated tag, string or comment means we can't reason about the structure.
+    match tokenizer.tokenize() {
+        Ok(()) => tokenizer.tokens,
+        Err(UnterminatedError) => Vec::new(),
+    }
+}
+
+struct TemplateTokenizer {
+    chars: Vec<char>,
+    pos: usize,
+    text: String,
+    tokens: Vec<TemplateToken>,
+    syntax: &'static Syntax,
+}
+
+impl TemplateTokenizer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, value: &str) -> bool {
+        value
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    fn flush_text(&mut self) {
+        if !self.text.is_empty() {
+            self.tokens
+                .push(TemplateToken::Text(std::mem::take(&mut self.text)));
+        }
+    }
+
+    fn tokenize(&mut self) -> Result<(), UnterminatedError> {
+        let syntax = self.syntax;
+
+        while let Some(c) = self.peek() {
+            if let Some(escape) = syntax.escapes.iter().find(|e| self.starts_with(e)) {
+                self.text.push_str(escape);
+                self.pos += escape.chars().count();
+            } else if let Some((open, close)) = syntax
+                .comments
+                .iter()
+                .find(|(open, _)| self.starts_with(open))
+            {
+                self.flush_text();
+                self.read_comment(open, close)?;
+            } else if let Some((open, close)) =
+                syntax.tags.iter().find(|(open, _)| self.starts_with(open))
+            {
+                self.flush_text();
+                self.read_tag(open, close)?;
+            } else {
+                self.text.push(c);
+                self.pos += 1;
+            }
+        }
+
+        self.flush_text();
+        Ok(())
+    }
+
+    fn read_comment(&mut self, open: &str, close: &str) -> Result<(), UnterminatedError> {
+        let mut comment = open.to_string();
+        self.pos += open.chars().count();
+        while let Some(c) = self.peek() {
+            if self.starts_with(close) {
+                comment.push_str(close);
+                self.pos += close.chars().count();
+                self.tokens.push(TemplateToken::Comment(comment));
+                return Ok(());
+            }
+            comment.push(c);
+            self.pos += 1;
+        }
+
+        Err(UnterminatedError)
+    }
+
+    fn read_tag(&mut self, open: &str, close: &str) -> Result<(), UnterminatedError> {
+        self.pos += open.chars().count();
+        self.tokens.push(TemplateToken::TagOpen(open.to_string()));
+        let first_token = self.tokens.len();
+        // Keeps track of brackets inside of the tag, for engines that only close a tag outside of them.
+        let mut depth = 0;
+
+        while let Some(c) = self.peek() {
+            if (depth == 0 || !self.syntax.balanced_brackets) && self.starts_with(close) {
+                self.pos += close.chars().count();
+                self.tokens.push(TemplateToken::TagClose(close.to_string()));
+                return self.read_raw_block(open, first_token);
+            }
+
+            match c {
+                c if c.is_whitespace() => self.pos += 1,
+                c if self.syntax.quotes.contains(&c) => self.read_string(c, close)?,
+                c if c.is_alphanumeric() || c == '_' || c == '$' => self.read_word(),
+                _ => {
+                    if matches!(c, '(' | '[' | '{') {
+                        depth += 1;
+                    } else if matches!(c, ')' | ']' | '}') && depth > 0 {
+                        depth -= 1;
+                    }
+                    self.pos += 1;
+                    self.tokens.push(TemplateToken::Punctuation(c.to_string()));
+                }
+            }
+        }
+
+        Err(UnterminatedError)
+    }
+
+    fn read_word(&mut self) {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if !c.is_alphanumeric() && c != '_' && c != '$' {
+                break;
+            }
+            word.push(c);
+            self.pos += 1;
+        }
+        self.tokens.push(TemplateToken::Word(word));
+    }
+
+    fn read_string(&mut self, quote: char, close: &str) -> Result<(), UnterminatedError> {
+        let mut string = quote.to_string();
+        self.pos += 1;
+        while let Some(c) = self.peek() {
+            if self.syntax.close_inside_strings && self.starts_with(close) {
+                // The tag ends here, so does the string.
+                self.tokens.push(TemplateToken::String(string));
+                return Ok(());
+            }
+            string.push(c);
+            self.pos += 1;
+            if c == '\\' {
+                if let Some(escaped) = self.peek() {
+                    string.push(escaped);
+                    self.pos += 1;
+                }
+            } else if c == quote {
+                self.tokens.push(TemplateToken::String(string));
+                return Ok(());
+            }
+        }
+
+        Err(UnterminatedError)
+    }
+
+    /* If the tag that was just read opens a raw block (e.g. `{% raw %}`), everything until the
+     * tag that ends it (e.g. `{% endraw %}`) is text. The end tag is tokenized as a regular tag.
+     */
+    fn read_raw_block(&mut self, open: &str, first_token: usize) -> Result<(), UnterminatedError> {
+        let name = self.tokens[first_token..]
+            .iter()
+            .find_map(|token| match token {
+                TemplateToken::Word(word) => Some(word),
+                _ => None,
+            });
+        let Some(name) =
+            name.filter(|name| open == "{%" && self.syntax.raw_blocks.contains(&name.as_str()))
+        else {
+            return Ok(());
+        };
+        let end = format!("end{}", name);
+
+        while let Some(c) = self.peek() {
+            if self.starts_with("{%") && self.is_raw_block_end(&end) {
+                self.flush_text();
+                return Ok(());
+            }
+            self.text.push(c);
+            self.pos += 1;
+        }
+
+        Err(UnterminatedError)
+    }
+
+    // Checks if the `{%` tag at the current position is e.g. `{% endraw %}` or `{%- endraw -%}`.
+    fn is_raw_block_end(&self, end: &str) -> bool {
+        let content = &self.chars[self.pos + 2..];
+        let Some(close) = content.windows(2).position(|w| w == ['%', '}']) else {
+            return false;
+        };
+        let content: String = content[..close].iter().collect();
+        content.trim_matches(|c: char| c.is_whitespace() || c == '-' || c == '+') == end
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use crate::ssti::helpers::select_template_engine_based_on_enum::TemplateEngine::{
+        Ejs, Handlebars, Jinja,
+    };
+    use crate::ssti::tokenize_template::tokenize_template;
+    use crate::ssti::tokenize_template::TemplateToken::{
+        Comment, Punctuation, String, TagClose, TagOpen, Text, Word,
+    };
+
+    macro_rules! text {
+        ($value:expr) => {
+            Text($value.to_string())
+        };
+    }
+
+    macro_rules! open {
+        ($value:expr) => {
+            TagOpen($value.to_string())
+        };
+    }
+
+    macro_rules! close {
+        ($value:expr) => {
+            TagClose($value.to_string())
+        };
+    }
+
+    macro_rules! word {
+        ($value:expr) => {
+            Word($value.to_string())
+        };
+    }
+
+    macro_rules! punct {
+        ($value:expr) => {
+            Punctuation($value.to_string())
+        };
+    }
+
+    #[test]
+    fn test_tokenize_text() {
+        assert_eq!(
+            tokenize_template("Hello world!", Handlebars),
+            vec![text!("Hello world!")]
+        );
+        assert_eq!(tokenize_template("", Jinja), vec![]);
+        assert_eq!(
+            tokenize_template("{ } % <> { %", Ejs),
+            vec![text!("{ } % <> { %")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_handlebars() {
+        assert_eq!(
+            tokenize_template("Hello {{user.name}}, {{{bio}}}!", Handlebars),
+            vec![
+                text!("Hello "),
+                open!("{{"),
+                word!("user"),
+                punct!("."),
+                word!("name"),
+                close!("}}"),
+                text!(", "),
+                open!("{{{"),
+                word!("bio"),
+                close!("}}}"),
+                text!("!"),
+            ]
+        );
+        assert_eq!(
+            tokenize_template("{{#if admin}}{{> panel}}{{/if}}", Handlebars),
+            vec![
+                open!("{{"),
+                punct!("#"),
+                word!("if"),
+                word!("admin"),
+                close!("}}"),
+                open!("{{"),
+                punct!(">"),
+                word!("panel"),
+                close!("}}"),
+                open!("{{"),
+                punct!("/"),
+                word!("if"),
+                close!("}}"),
+            ]
+        );
+        assert_eq!(
+            tokenize_template("{{t \"}} {{\"}}", Handlebars),
+            vec![
+                open!("{{"),
+                word!("t"),
+                String("\"}} {{\"".to_string()),
+                close!("}}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_handlebars_comments_and_escapes() {
+        assert_eq!(
+            tokenize_template("{{! note }}{{!-- {{a}} --}}\\{{b}}", Handlebars),
+            vec![
+                Comment("{{! note }}".to_string()),
+                Comment("{{!-- {{a}} --}}".to_string()),
+                text!("\\{{b}}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_ejs() {
+        assert_eq!(
+            tokenize_template("<% if (user) { %><%= user.name %><%- html -%><% } %>", Ejs),
+            vec![
+                open!("<%"),
+                word!("if"),
+                punct!("("),
+                word!("user"),
+                punct!(")"),
+                punct!("{"),
+                close!("%>"),
+                open!("<%="),
+                word!("user"),
+                punct!("."),
+                word!("name"),
+                close!("%>"),
+                open!("<%-"),
+                word!("html"),
+                punct!("-"),
+                close!("%>"),
+                open!("<%"),
+                punct!("}"),
+                close!("%>"),
+            ]
+        );
+        assert_eq!(
+            tokenize_template("<%# note %>50<%% off", Ejs),
+            vec![Comment("<%# note %>".to_string()), text!("50<%% off")]
+        );
+        // EJS ends the tag at the first `%>`, even inside of a string
+        assert_eq!(
+            tokenize_template("<%= \"a%>b\" %>", Ejs),
+            vec![
+                open!("<%="),
+                String("\"a".to_string()),
+                close!("%>"),
+                text!("b\" %>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_jinja() {
+        assert_eq!(
+            tokenize_template(
+                "{% for x in items -%}{{ x|e }}{% endfor %}{# note #}",
+                Jinja
+            ),
+            vec![
+                open!("{%"),
+                word!("for"),
+                word!("x"),
+                word!("in"),
+                word!("items"),
+                punct!("-"),
+                close!("%}"),
+                open!("{{"),
+                word!("x"),
+                punct!("|"),
+                word!("e"),
+                close!("}}"),
+                open!("{%"),
+                word!("endfor"),
+                close!("%}"),
+                Comment("{# note #}".to_string()),
+            ]
+        );
+        assert_eq!(
+            tokenize_template("{{ {'a': {'b': 1}} }}", Jinja),
+            vec![
+                open!("{{"),
+                punct!("{"),
+                String("'a'".to_string()),
+                punct!(":"),
+                punct!("{"),
+                String("'b'".to_string()),
+                punct!(":"),
+                word!("1"),
+                punct!("}"),
+                punct!("}"),
+                close!("}}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_jinja_raw_blocks() {
+        assert_eq!(
+            tokenize_template("{% raw %}{{ a }}{% b %}{%- endraw -%}", Jinja),
+            vec![
+                open!("{%"),
+                word!("raw"),
+                close!("%}"),
+                text!("{{ a }}{% b %}"),
+                open!("{%"),
+                punct!("-"),
+                word!("endraw"),
+                punct!("-"),
+                close!("%}"),
+            ]
+        );
+        assert_eq!(
+            tokenize_template("{% verbatim %}{{ a }}{% endverbatim %}", Jinja).len(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unterminated() {
+        assert_eq!(tokenize_template("Hello {{name", Handlebars), vec![]);
+        assert_eq!(tokenize_template("{{ 'name }}", Jinja), vec![]);
+        assert_eq!(tokenize_template("{# note", Jinja), vec![]);
+        assert_eq!(tokenize_template("{% raw %}{{ a }}", Jinja), vec![]);
+        assert_eq!(tokenize_template("<%= name", Ejs), vec![]);
+    }
+}
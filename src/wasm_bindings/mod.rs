@@ -1,10 +1,45 @@
 use crate::idor::idor_analyze_sql::idor_analyze_sql;
-use crate::js_injection::detect_js_injection::detect_js_injection_str;
-use crate::sql_injection::detect_sql_injection::{detect_sql_injection_str, DetectionReason};
+use crate::js_injection::detect_js_injection::{
+    detect_js_injection_per_occurrence, detect_js_injection_str,
+};
+use crate::nosql_injection::detect_nosql_injection::detect_nosql_injection_str;
+use crate::path_traversal::detect_path_traversal::{
+    detect_path_traversal, DetectionReason as PathTraversalReason,
+};
+use crate::shell_injection::detect_shell_injection::detect_shell_injection_str;
+use crate::sql_injection::custom_exemptions::set_custom_exemptions_json;
+use crate::sql_injection::detect_sql_injection::{
+    detect_sql_injection_auto_dialect, detect_sql_injection_batch,
+    detect_sql_injection_per_occurrence, detect_sql_injection_str,
+    detect_sql_injection_with_decodings, detect_sql_injection_with_max_len, DetectionReason,
+};
+use crate::sql_injection::explain_sql_injection::explain_sql_injection_str;
+use crate::sql_injection::helpers::select_decodings_based_on_bitmask::select_decodings_based_on_bitmask;
+use crate::sql_injection::sql_dialect::SqlDialect;
+use crate::sql_injection::sql_injection_detector::SqlInjectionDetector;
+use crate::sql_injection::user_input_context::get_user_input_contexts;
+use crate::ssrf::ssrf_analyze_url::ssrf_analyze_url;
+use crate::ssti::detect_ssti::detect_ssti_str;
 use wasm_bindgen::prelude::*;
 
+// Checks that `dialect` is one of the numbers of `SqlDialect`, e.g. `Unknown SQL dialect: 99`.
+fn validate_dialect(dialect: i32) -> Result<(), String> {
+    SqlDialect::try_from(dialect).map(|_| ())
+}
+
+// Returns `{ error }` for the functions that return an object, e.g. for an unknown SQL dialect.
+fn error_object(error: String) -> JsValue {
+    let obj = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&obj, &"error".into(), &error.into());
+    obj.into()
+}
+
 #[wasm_bindgen]
 pub fn wasm_detect_sql_injection(query: &str, userinput: &str, dialect: i32) -> i32 {
+    if validate_dialect(dialect).is_err() {
+        return 4;
+    }
+
     let detection_results = detect_sql_injection_str(query, userinput, dialect);
 
     if let DetectionReason::FailedToTokenizeQuery = detection_results.reason {
@@ -19,19 +54,249 @@ pub fn wasm_detect_sql_injection(query: &str, userinput: &str, dialect: i32) ->
     }
 }
 
+// Same as wasm_detect_sql_injection, but the tokens of up to `capacity` queries are cached.
+#[wasm_bindgen(js_name = SqlInjectionDetector)]
+pub struct WasmSqlInjectionDetector {
+    detector: SqlInjectionDetector,
+}
+
+#[wasm_bindgen(js_class = SqlInjectionDetector)]
+impl WasmSqlInjectionDetector {
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: usize) -> WasmSqlInjectionDetector {
+        WasmSqlInjectionDetector {
+            detector: SqlInjectionDetector::new(capacity),
+        }
+    }
+
+    pub fn detect(&self, query: &str, userinput: &str, dialect: i32) -> i32 {
+        if validate_dialect(dialect).is_err() {
+            return 4;
+        }
+
+        let detection_results = self.detector.detect(query, userinput, dialect);
+
+        if let DetectionReason::FailedToTokenizeQuery = detection_results.reason {
+            return 3;
+        }
+
+        if detection_results.detected {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+// Same as wasm_detect_sql_injection, but returns 4 if the dialect is unknown.
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_with_dialect_name(
+    query: &str,
+    userinput: &str,
+    dialect: &str,
+) -> i32 {
+    let dialect = match dialect.parse::<SqlDialect>() {
+        Ok(dialect) => dialect,
+        Err(_) => return 4,
+    };
+
+    wasm_detect_sql_injection(query, userinput, dialect as i32)
+}
+
+// Returns the number of the SQL dialect with the given name, or -1 if the dialect is unknown.
+#[wasm_bindgen]
+pub fn wasm_sql_dialect_from_name(name: &str) -> i32 {
+    name.parse::<SqlDialect>()
+        .map_or(-1, |dialect| dialect as i32)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_details(query: &str, userinput: &str, dialect: i32) -> JsValue {
+    if let Err(e) = validate_dialect(dialect) {
+        return error_object(e);
+    }
+
+    let detection_results = detect_sql_injection_str(query, userinput, dialect);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_with_decodings(
+    query: &str,
+    userinput: &str,
+    dialect: i32,
+    decodings: i32,
+) -> JsValue {
+    if let Err(e) = validate_dialect(dialect) {
+        return error_object(e);
+    }
+
+    let detection_results = detect_sql_injection_with_decodings(
+        query,
+        userinput,
+        dialect,
+        &select_decodings_based_on_bitmask(decodings),
+    );
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_per_occurrence(
+    query: &str,
+    userinput: &str,
+    dialect: i32,
+) -> JsValue {
+    if let Err(e) = validate_dialect(dialect) {
+        return error_object(e);
+    }
+
+    let detection_results = detect_sql_injection_per_occurrence(query, userinput, dialect);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_with_max_len(
+    query: &str,
+    userinput: &str,
+    dialect: i32,
+    max_len: usize,
+) -> JsValue {
+    if let Err(e) = validate_dialect(dialect) {
+        return error_object(e);
+    }
+
+    let detection_results = detect_sql_injection_with_max_len(query, userinput, dialect, max_len);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_auto_dialect(query: &str, userinput: &str) -> JsValue {
+    let detection_results = detect_sql_injection_auto_dialect(query, userinput);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_get_sql_user_input_contexts(query: &str, userinput: &str, dialect: i32) -> JsValue {
+    match validate_dialect(dialect)
+        .and_then(|()| get_user_input_contexts(query, userinput, dialect))
+    {
+        Ok(occurrences) => serde_wasm_bindgen::to_value(&occurrences).unwrap_or(JsValue::NULL),
+        Err(e) => error_object(e),
+    }
+}
+
+// Replaces the custom SQL injection exemptions, for every detection of the WASM instance.
+#[wasm_bindgen]
+pub fn wasm_set_sql_injection_exemptions(exemptions: &str) -> JsValue {
+    match set_custom_exemptions_json(exemptions) {
+        Ok(()) => JsValue::UNDEFINED,
+        Err(e) => error_object(e),
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_explain_sql_injection(query: &str, userinput: &str, dialect: i32) -> JsValue {
+    if let Err(e) = validate_dialect(dialect) {
+        return error_object(e);
+    }
+
+    let explanation = explain_sql_injection_str(query, userinput, dialect);
+    serde_wasm_bindgen::to_value(&explanation).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_sql_injection_batch(
+    query: &str,
+    userinputs: Vec<String>,
+    dialect: i32,
+) -> JsValue {
+    if let Err(e) = validate_dialect(dialect) {
+        return error_object(e);
+    }
+
+    let userinputs: Vec<&str> = userinputs.iter().map(String::as_str).collect();
+    let detection_results = detect_sql_injection_batch(query, &userinputs, dialect);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
 #[wasm_bindgen]
 pub fn wasm_detect_js_injection(code: &str, userinput: &str, sourcetype: i32) -> bool {
-    detect_js_injection_str(code, userinput, sourcetype)
+    detect_js_injection_str(code, userinput, sourcetype).detected
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_js_injection_details(code: &str, userinput: &str, sourcetype: i32) -> JsValue {
+    let detection_results = detect_js_injection_str(code, userinput, sourcetype);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_js_injection_per_occurrence(
+    code: &str,
+    userinput: &str,
+    sourcetype: i32,
+) -> JsValue {
+    let detection_results = detect_js_injection_per_occurrence(code, userinput, sourcetype);
+    serde_wasm_bindgen::to_value(&detection_results).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_shell_injection(command: &str, userinput: &str) -> bool {
+    detect_shell_injection_str(command, userinput)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_ssti(template: &str, userinput: &str, engine: i32) -> bool {
+    detect_ssti_str(template, userinput, engine)
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_path_traversal(file_path: &str, userinput: &str, os_flavor: i32) -> i32 {
+    let detection_results = detect_path_traversal(file_path, userinput, os_flavor);
+
+    if let PathTraversalReason::FailedToDecodeUserInput = detection_results.reason {
+        // make a special exception for failing to decode the user input (report code 3)
+        return 3;
+    }
+
+    if detection_results.detected {
+        1
+    } else {
+        0
+    }
 }
 
 #[wasm_bindgen]
 pub fn wasm_idor_analyze_sql(query: &str, dialect: i32) -> JsValue {
-    match idor_analyze_sql(query, dialect) {
+    match validate_dialect(dialect).and_then(|()| idor_analyze_sql(query, dialect)) {
         Ok(selects) => serde_wasm_bindgen::to_value(&selects).unwrap_or(JsValue::NULL),
-        Err(e) => {
-            let obj = js_sys::Object::new();
-            let _ = js_sys::Reflect::set(&obj, &"error".into(), &e.into());
-            obj.into()
-        }
+        Err(e) => error_object(e),
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_idor_analyze_sql_with_dialect_name(query: &str, dialect: &str) -> JsValue {
+    match dialect
+        .parse::<SqlDialect>()
+        .and_then(|dialect| idor_analyze_sql(query, dialect as i32))
+    {
+        Ok(selects) => serde_wasm_bindgen::to_value(&selects).unwrap_or(JsValue::NULL),
+        Err(e) => error_object(e),
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_ssrf_analyze_url(url: &str) -> JsValue {
+    match ssrf_analyze_url(url) {
+        Ok(result) => serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL),
+        Err(e) => error_object(e),
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_detect_nosql_injection(filter: &str, userinput: &str) -> JsValue {
+    match detect_nosql_injection_str(filter, userinput) {
+        Ok(result) => serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL),
+        Err(e) => error_object(e),
     }
 }